@@ -0,0 +1,23 @@
+//! Uses the parts of `hex-game` that don't need `std` from a `#![no_std]` crate, so that building
+//! this crate fails if any of them start to need it.
+
+#![no_std]
+
+use hex_game::arrayboard::ArrayBoard;
+use hex_game::bitboard::BitBoard;
+use hex_game::cell::{Color, GameStatus};
+use hex_game::coord::Coord;
+
+/// Plays Black across the middle row of both fixed-size boards, starting from a parsed coordinate,
+/// and returns the status of each afterwards, which should be a win for Black.
+pub fn play_middle_row() -> (GameStatus, GameStatus) {
+    let mut array_board = ArrayBoard::<5>::new();
+    let mut bit_board = BitBoard::new(5);
+    let start: Coord = "a3".parse().unwrap();
+    for x in 0..5 {
+        let coord = Coord{x, y: start.y};
+        array_board.place_piece(coord, Color::Black);
+        bit_board.set(coord, Color::Black);
+    }
+    (array_board.status(), bit_board.status())
+}
@@ -0,0 +1,256 @@
+//! A Hex board whose size is a compile-time constant. This has the same basic interface as `Board`,
+//! but stores its cells and union-find structures in fixed-size arrays instead of sets, so that
+//! placing a piece never hashes or allocates. This makes it a better fit for playing out many
+//! random games, at the cost of the analysis methods `Board` provides.
+
+use crate::cell::{Color, GameStatus, HexCell};
+use crate::coord::Coord;
+
+/// A union-find structure over the cells of an `N`x`N` board plus two virtual nodes for a player's
+/// edges. Node `y * N + x` is the cell at `(x, y)`, and nodes `N * N` and `N * N + 1` are the first
+/// and second edges.
+#[derive(Copy, Clone, Debug)]
+struct Groups<const N: usize> {
+    /// The parent of each cell, indexed by row and then column.
+    cells: [[u16; N]; N],
+    /// The parents of the two edges.
+    edges: [u16; 2],
+}
+
+impl<const N: usize> Groups<N> {
+    /// The node for the first edge: the left edge for Black, or the top edge for White.
+    const FIRST_EDGE: u16 = (N * N) as u16;
+    /// The node for the second edge: the right edge for Black, or the bottom edge for White.
+    const SECOND_EDGE: u16 = (N * N) as u16 + 1;
+
+    /// Returns a union-find in which every node is in its own group.
+    fn new() -> Groups<N> {
+        let mut cells = [[0; N]; N];
+        for (y, row) in cells.iter_mut().enumerate() {
+            for (x, parent) in row.iter_mut().enumerate() {
+                *parent = (y * N + x) as u16;
+            }
+        }
+        Groups {
+            cells,
+            edges: [Self::FIRST_EDGE, Self::SECOND_EDGE],
+        }
+    }
+    /// Returns a mutable reference to the parent of the given node.
+    fn parent_mut(&mut self, node: u16) -> &mut u16 {
+        let node = usize::from(node);
+        if node < N * N {
+            &mut self.cells[node / N][node % N]
+        } else {
+            &mut self.edges[node - N * N]
+        }
+    }
+    /// Returns the representative of the given node's group, halving the path to it along the way.
+    fn find(&mut self, mut node: u16) -> u16 {
+        loop {
+            let parent = *self.parent_mut(node);
+            if parent == node {
+                return node;
+            }
+            let grandparent = *self.parent_mut(parent);
+            *self.parent_mut(node) = grandparent;
+            node = grandparent;
+        }
+    }
+    /// Joins the groups of the two nodes.
+    fn union(&mut self, a: u16, b: u16) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            *self.parent_mut(a) = b;
+        }
+    }
+}
+
+/// A Hex board of size `N`, which must be between 1 and 26. As with `Board`, Black connects the left
+/// and right edges and White connects the top and bottom edges.
+#[derive(Copy, Clone, Debug)]
+pub struct ArrayBoard<const N: usize> {
+    /// The contents of each cell, indexed by row and then column.
+    cells: [[HexCell; N]; N],
+    /// Black's groups of pieces, including the left and right edges.
+    black_groups: Groups<N>,
+    /// White's groups of pieces, including the top and bottom edges.
+    white_groups: Groups<N>,
+    /// The current status of the board: black win, white win, or ongoing.
+    status: GameStatus,
+}
+
+impl<const N: usize> Default for ArrayBoard<N> {
+    fn default() -> Self {
+        ArrayBoard::new()
+    }
+}
+
+impl<const N: usize> ArrayBoard<N> {
+    /// Initializes a blank board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0 or larger than 26.
+    pub fn new() -> ArrayBoard<N> {
+        assert!((1..=26).contains(&N), "invalid board size {}: must be between 1 and 26", N);
+        ArrayBoard {
+            cells: [[HexCell::Empty; N]; N],
+            black_groups: Groups::new(),
+            white_groups: Groups::new(),
+            status: GameStatus::Ongoing,
+        }
+    }
+    /// Returns the size of the board: both width and length.
+    pub fn size(&self) -> usize {
+        N
+    }
+    /// Returns a `HexCell` value describing the piece at the given location: `Empty` if no piece is
+    /// there, `Black` if Black has a piece, or `White` if White has a piece. If the coordinate is out
+    /// of bounds, returns `Empty`.
+    pub fn piece(&self, coord: Coord) -> HexCell {
+        self.cells.get(usize::from(coord.y))
+            .and_then(|row| row.get(usize::from(coord.x)))
+            .cloned()
+            .unwrap_or(HexCell::Empty)
+    }
+    /// Places the piece at the given spot if the placement is valid (there are no other pieces and
+    /// the coordinate is within range), modifying the board's state and returning true. Otherwise,
+    /// does not modify the board state and returns false.
+    pub fn place_piece(&mut self, coord: Coord, color: Color) -> bool {
+        let (x, y) = (usize::from(coord.x), usize::from(coord.y));
+        if x >= N || y >= N || self.cells[y][x] != HexCell::Empty {
+            return false;
+        }
+        let (cell, groups) = match color {
+            Color::Black => (HexCell::Black, &mut self.black_groups),
+            Color::White => (HexCell::White, &mut self.white_groups),
+        };
+        self.cells[y][x] = cell;
+        let node = (y * N + x) as u16;
+        for neighbor in coord.neighbors_iter(N as u8) {
+            if self.cells[usize::from(neighbor.y)][usize::from(neighbor.x)] == cell {
+                groups.union(node, u16::from(neighbor.y) * N as u16 + u16::from(neighbor.x));
+            }
+        }
+        // the first and second edges: left and right for Black, top and bottom for White
+        let (along, last) = match color {
+            Color::Black => (x, N - 1),
+            Color::White => (y, N - 1),
+        };
+        if along == 0 {
+            groups.union(node, Groups::<N>::FIRST_EDGE);
+        }
+        if along == last {
+            groups.union(node, Groups::<N>::SECOND_EDGE);
+        }
+        // only the player who just moved can have won
+        if groups.find(Groups::<N>::FIRST_EDGE) == groups.find(Groups::<N>::SECOND_EDGE) {
+            self.status = match color {
+                Color::Black => GameStatus::BlackWin,
+                Color::White => GameStatus::WhiteWin,
+            };
+        }
+        true
+    }
+    /// Returns the current game status. This is updated automatically as pieces are placed, so this
+    /// function has basically no runtime cost.
+    pub fn status(&self) -> GameStatus {
+        self.status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::board::Board;
+    #[cfg(feature = "std")]
+    use crate::testutil::Lcg;
+
+    #[test]
+    fn test_game_status() {
+        let mut board = ArrayBoard::<5>::new();
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 0, y: 2}, Color::White);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 4}, Color::White);
+        board.place_piece(Coord{x: 1, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 4, y: 1}, Color::White);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 0}, Color::White);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 4, y: 3}, Color::White);
+        board.place_piece(Coord{x: 3, y: 1}, Color::Black);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 4, y: 0}, Color::Black);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+
+        let mut board2 = ArrayBoard::<5>::new();
+        board2.place_piece(Coord{x: 0, y: 0}, Color::White);
+        board2.place_piece(Coord{x: 2, y: 0}, Color::Black);
+        board2.place_piece(Coord{x: 1, y: 0}, Color::White);
+        board2.place_piece(Coord{x: 4, y: 2}, Color::Black);
+        board2.place_piece(Coord{x: 1, y: 1}, Color::White);
+        board2.place_piece(Coord{x: 1, y: 4}, Color::Black);
+        board2.place_piece(Coord{x: 2, y: 1}, Color::White);
+        board2.place_piece(Coord{x: 0, y: 3}, Color::Black);
+        board2.place_piece(Coord{x: 2, y: 2}, Color::White);
+        board2.place_piece(Coord{x: 3, y: 4}, Color::Black);
+        board2.place_piece(Coord{x: 1, y: 3}, Color::White);
+        assert_eq!(board2.status(), GameStatus::Ongoing);
+        board2.place_piece(Coord{x: 0, y: 4}, Color::White);
+        assert_eq!(board2.status(), GameStatus::WhiteWin);
+    }
+
+    #[test]
+    fn test_white_vertical_chain() {
+        let mut board = ArrayBoard::<5>::new();
+        for y in 0..4 {
+            board.place_piece(Coord{x: 3, y}, Color::White);
+        }
+        // the chain stops one short of the bottom edge
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 4, y: 4}, Color::White);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 2, y: 4}, Color::White);
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+    }
+
+    #[test]
+    fn test_place_piece() {
+        let mut board = ArrayBoard::<5>::new();
+        assert_eq!(board.size(), 5);
+        assert!(board.place_piece(Coord{x: 2, y: 3}, Color::Black));
+        assert_eq!(board.piece(Coord{x: 2, y: 3}), HexCell::Black);
+        // occupied and out-of-bounds cells can't be played
+        assert!(!board.place_piece(Coord{x: 2, y: 3}, Color::White));
+        assert!(!board.place_piece(Coord{x: 5, y: 0}, Color::White));
+        assert_eq!(board.piece(Coord{x: 5, y: 0}), HexCell::Empty);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_matches_board() {
+        let mut rng = Lcg::new(12345);
+        for _ in 0..50 {
+            let cells = rng.shuffled_cells(5);
+            let mut board = Board::new(5);
+            let mut array_board = ArrayBoard::<5>::new();
+            for (i, &coord) in cells.iter().enumerate() {
+                let color = if i % 2 == 0 { Color::Black } else { Color::White };
+                board.place_piece(coord, color);
+                array_board.place_piece(coord, color);
+                assert_eq!(array_board.status(), board.status());
+                assert_eq!(array_board.piece(coord), board.piece(coord));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_large() {
+        ArrayBoard::<27>::new();
+    }
+}
@@ -0,0 +1,149 @@
+//! Reading and writing Hex games in a compact binary format, for storing large numbers of games. A
+//! record is one byte for the board size, one byte that is 1 if White swapped and 0 otherwise, and
+//! then each move other than the swap as the index `y * size + x` of its cell, written as a
+//! little-endian base-128 varint: seven bits per byte, with the high bit set on every byte but the
+//! last. Indices below 128 take one byte, and every index on a 26x26 board fits in two. Only the
+//! moves are stored, not resignations or any metadata.
+
+use std::error;
+use std::fmt;
+
+use crate::coord::Coord;
+use crate::game::{Game, Move, ReplayError};
+
+/// An error for reading a game from the binary format.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DecodeError {
+    /// The input ended partway through the header or a move.
+    UnexpectedEnd,
+    /// The size byte is the given value, which isn't a valid board size.
+    InvalidSize(u8),
+    /// The swap flag is the given value instead of 0 or 1.
+    InvalidSwapFlag(u8),
+    /// The move at the given index in the record, not counting the swap, isn't a cell on the board.
+    InvalidMove(usize),
+    /// One of the moves couldn't be made.
+    IllegalMove(ReplayError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::UnexpectedEnd => write!(f, "binary game record ends unexpectedly"),
+            DecodeError::InvalidSize(size) => write!(f, "invalid board size {} in binary game record", size),
+            DecodeError::InvalidSwapFlag(flag) => write!(f, "invalid swap flag {} in binary game record", flag),
+            DecodeError::InvalidMove(index) => write!(f, "move at index {} is not on the board", index),
+            DecodeError::IllegalMove(ref error) => error.fmt(f),
+        }
+    }
+}
+
+impl error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DecodeError::IllegalMove(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Game {
+    /// Returns this game in the compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.board_size, self.swapped() as u8];
+        for m in &self.moves {
+            if let Move::Place(coord) = *m {
+                let mut index = u32::from(coord.y) * u32::from(self.board_size) + u32::from(coord.x);
+                while index >= 0x80 {
+                    bytes.push((index & 0x7f) as u8 | 0x80);
+                    index >>= 7;
+                }
+                bytes.push(index as u8);
+            }
+        }
+        bytes
+    }
+    /// Reads a game in the compact binary format, replaying each move. Fails if the input is cut
+    /// off, has an invalid header, or has a move that isn't on the board or can't be made.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Game, DecodeError> {
+        let (&size, &swap_flag) = match bytes {
+            [size, swap_flag, ..] => (size, swap_flag),
+            _ => return Err(DecodeError::UnexpectedEnd),
+        };
+        if Game::try_new(size).is_err() {
+            return Err(DecodeError::InvalidSize(size));
+        }
+        let swapped = match swap_flag {
+            0 => false,
+            1 => true,
+            flag => return Err(DecodeError::InvalidSwapFlag(flag)),
+        };
+        let cells = u32::from(size) * u32::from(size);
+        let mut moves = vec![];
+        let mut rest = bytes[2..].iter();
+        while rest.len() > 0 {
+            let mut index: u32 = 0;
+            let mut shift = 0;
+            loop {
+                let byte = *rest.next().ok_or(DecodeError::UnexpectedEnd)?;
+                // an index needing more than three bytes is too large for any board
+                if shift > 14 {
+                    return Err(DecodeError::InvalidMove(moves.len()));
+                }
+                index |= u32::from(byte & 0x7f) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            if index >= cells {
+                return Err(DecodeError::InvalidMove(moves.len()));
+            }
+            let (x, y) = (index % u32::from(size), index / u32::from(size));
+            moves.push(Coord{x: x as u8, y: y as u8});
+        }
+        Game::from_moves(size, &moves, swapped).map_err(DecodeError::IllegalMove)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::game::MoveError;
+
+    #[test]
+    fn test_round_trip() {
+        let mut g = Game::new(13);
+        g.make_move(Coord{x: 6, y: 6});
+        g.swap();
+        for &(x, y) in &[(0, 0), (12, 12), (5, 7)] {
+            g.make_move(Coord{x, y});
+        }
+        let bytes = g.to_bytes();
+        // the header, then one byte for each move except m13, whose index of 168 takes two
+        assert_eq!(bytes, vec![13, 1, 84, 0, 0xa8, 0x01, 96]);
+        let read = Game::from_bytes(&bytes).unwrap();
+        assert_eq!(read.moves, g.moves);
+        assert_eq!(read.board(), g.board());
+        assert!(g.to_bytes().len() < g.to_sgf(&Default::default()).len() / 4);
+        assert_eq!(Game::from_bytes(&[5, 0]).unwrap().moves, vec![]);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(Game::from_bytes(&[]).unwrap_err(), DecodeError::UnexpectedEnd);
+        assert_eq!(Game::from_bytes(&[5]).unwrap_err(), DecodeError::UnexpectedEnd);
+        assert_eq!(Game::from_bytes(&[0, 0]).unwrap_err(), DecodeError::InvalidSize(0));
+        assert_eq!(Game::from_bytes(&[27, 0]).unwrap_err(), DecodeError::InvalidSize(27));
+        assert_eq!(Game::from_bytes(&[5, 2]).unwrap_err(), DecodeError::InvalidSwapFlag(2));
+        // a varint cut off partway through, and one past the end of the board
+        assert_eq!(Game::from_bytes(&[13, 0, 0x80]).unwrap_err(), DecodeError::UnexpectedEnd);
+        assert_eq!(Game::from_bytes(&[5, 0, 3, 25]).unwrap_err(), DecodeError::InvalidMove(1));
+        assert_eq!(Game::from_bytes(&[5, 0, 0xff, 0xff, 0xff, 0x01]).unwrap_err(), DecodeError::InvalidMove(0));
+        let occupied = ReplayError::Move { index: 1, error: MoveError::Occupied(crate::board::HexCell::Black) };
+        assert_eq!(Game::from_bytes(&[5, 0, 3, 3]).unwrap_err(), DecodeError::IllegalMove(occupied));
+        assert_eq!(Game::from_bytes(&[5, 1]).unwrap_err(),
+                   DecodeError::IllegalMove(ReplayError::Move { index: 0, error: MoveError::InvalidSwap }));
+    }
+}
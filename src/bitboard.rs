@@ -0,0 +1,219 @@
+//! A Hex board for sizes up to 11x11 that stores each color's pieces as the bits of a `u128`. Finding
+//! the neighbors of a whole set of cells, counting pieces, and checking for a win then take only a
+//! handful of bitwise operations each, without any branching on individual cells.
+//!
+//! Cell `(x, y)` is bit `y * size + x`, so bits go left to right along the top row, then along the
+//! next row, and so on. A mask is any `u128` using this layout.
+
+use crate::cell::{Color, GameStatus, HexCell};
+use crate::coord::Coord;
+
+/// The largest board that fits into a `u128`.
+pub const MAX_BITBOARD_SIZE: u8 = 11;
+
+/// A Hex board of up to 11x11 with each color's pieces stored as a bitmask. As with `Board`, Black
+/// connects the left and right edges and White connects the top and bottom edges. Unlike `Board`,
+/// the status isn't stored, and is found from scratch by `status`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitBoard {
+    /// The number of hexes on one edge of the board.
+    size: u8,
+    /// Black's pieces.
+    black: u128,
+    /// White's pieces.
+    white: u128,
+}
+
+impl BitBoard {
+    /// Initializes a blank board of the given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or larger than 11.
+    pub fn new(size: u8) -> BitBoard {
+        assert!((1..=MAX_BITBOARD_SIZE).contains(&size),
+                "invalid bitboard size {}: must be between 1 and {}", size, MAX_BITBOARD_SIZE);
+        BitBoard { size, black: 0, white: 0 }
+    }
+    /// Returns the number of hexes on one edge of the board.
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+    /// Returns the mask with the bit for the given coordinate set, or `None` if it's outside the
+    /// board.
+    fn bit(&self, coord: Coord) -> Option<u128> {
+        if coord.x < self.size && coord.y < self.size {
+            Some(1 << (u32::from(coord.y) * u32::from(self.size) + u32::from(coord.x)))
+        } else {
+            None
+        }
+    }
+    /// Returns the mask of every cell on the board.
+    pub fn board_mask(&self) -> u128 {
+        let cells = u32::from(self.size) * u32::from(self.size);
+        // a full 11x11 board still leaves 7 bits spare, so this can't overflow
+        (1 << cells) - 1
+    }
+    /// Returns the mask of the cells in the given column.
+    fn column_mask(&self, x: u8) -> u128 {
+        (0..self.size).fold(0, |mask, y| mask | self.bit(Coord{x, y}).unwrap())
+    }
+    /// Returns the mask of the cells in the given row.
+    fn row_mask(&self, y: u8) -> u128 {
+        (0..self.size).fold(0, |mask, x| mask | self.bit(Coord{x, y}).unwrap())
+    }
+    /// Returns the masks of the cells on the given color's first and second edges: the left and
+    /// right columns for Black, or the top and bottom rows for White.
+    fn edge_masks(&self, color: Color) -> (u128, u128) {
+        match color {
+            Color::Black => (self.column_mask(0), self.column_mask(self.size - 1)),
+            Color::White => (self.row_mask(0), self.row_mask(self.size - 1)),
+        }
+    }
+    /// Places a piece of the given color at the given spot if the placement is valid (there are no
+    /// other pieces and the coordinate is within range), returning true. Otherwise, does not modify
+    /// the board and returns false.
+    pub fn set(&mut self, coord: Coord, color: Color) -> bool {
+        match self.bit(coord) {
+            Some(bit) if (self.black | self.white) & bit == 0 => {
+                match color {
+                    Color::Black => self.black |= bit,
+                    Color::White => self.white |= bit,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Returns a `HexCell` value describing the piece at the given location: `Empty` if no piece is
+    /// there, `Black` if Black has a piece, or `White` if White has a piece. If the coordinate is out
+    /// of bounds, returns `Empty`.
+    pub fn get(&self, coord: Coord) -> HexCell {
+        match self.bit(coord) {
+            Some(bit) if self.black & bit != 0 => HexCell::Black,
+            Some(bit) if self.white & bit != 0 => HexCell::White,
+            _ => HexCell::Empty,
+        }
+    }
+    /// Returns the mask of the given color's pieces.
+    pub fn pieces_mask(&self, color: Color) -> u128 {
+        match color {
+            Color::Black => self.black,
+            Color::White => self.white,
+        }
+    }
+    /// Returns the number of pieces of the given color on the board.
+    pub fn piece_count(&self, color: Color) -> u32 {
+        self.pieces_mask(color).count_ones()
+    }
+    /// Returns the mask of every cell that neighbors at least one of the cells in the given mask,
+    /// which can include cells in the mask itself.
+    pub fn neighbors_mask(&self, mask: u128) -> u128 {
+        let size = u32::from(self.size);
+        // moving right or left can wrap around onto the next or previous row, so these columns have
+        // to be cleared afterwards
+        let not_first = !self.column_mask(0);
+        let not_last = !self.column_mask(self.size - 1);
+        let up = mask >> size;
+        let up_right = (mask >> (size - 1)) & not_first;
+        let right = (mask << 1) & not_first;
+        let down = mask << size;
+        let down_left = (mask << (size - 1)) & not_last;
+        let left = (mask >> 1) & not_last;
+        (up | up_right | right | down | down_left | left) & self.board_mask()
+    }
+    /// Returns whether the given color's pieces connect its two edges, by flood-filling outwards from
+    /// the pieces on its first edge until the fill stops growing.
+    pub fn is_connected(&self, color: Color) -> bool {
+        let pieces = self.pieces_mask(color);
+        let (first, second) = self.edge_masks(color);
+        let mut reached = pieces & first;
+        loop {
+            if reached & second != 0 {
+                return true;
+            }
+            let next = (reached | self.neighbors_mask(reached)) & pieces;
+            if next == reached {
+                return false;
+            }
+            reached = next;
+        }
+    }
+    /// Returns the current game status: `BlackWin` or `WhiteWin` if either player has connected
+    /// their edges, or `Ongoing` otherwise.
+    pub fn status(&self) -> GameStatus {
+        if self.is_connected(Color::Black) {
+            GameStatus::BlackWin
+        } else if self.is_connected(Color::White) {
+            GameStatus::WhiteWin
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::board::Board;
+    #[cfg(feature = "std")]
+    use crate::testutil::Lcg;
+
+    #[test]
+    fn test_set_get() {
+        let mut board = BitBoard::new(5);
+        assert!(board.set(Coord{x: 2, y: 3}, Color::Black));
+        assert!(board.set(Coord{x: 4, y: 4}, Color::White));
+        assert_eq!(board.get(Coord{x: 2, y: 3}), HexCell::Black);
+        assert_eq!(board.get(Coord{x: 4, y: 4}), HexCell::White);
+        assert_eq!(board.get(Coord{x: 0, y: 0}), HexCell::Empty);
+        assert!(!board.set(Coord{x: 2, y: 3}, Color::White));
+        assert!(!board.set(Coord{x: 5, y: 0}, Color::White));
+        assert_eq!(board.get(Coord{x: 5, y: 0}), HexCell::Empty);
+        assert_eq!(board.piece_count(Color::Black), 1);
+        assert_eq!(board.pieces_mask(Color::White), 1 << 24);
+    }
+
+    #[test]
+    fn test_neighbors_mask() {
+        for size in 1..=MAX_BITBOARD_SIZE {
+            let board = BitBoard::new(size);
+            for y in 0..size {
+                for x in 0..size {
+                    let coord = Coord{x, y};
+                    let expected = coord.neighbors_within(size).into_iter()
+                        .fold(0, |mask, c| mask | board.bit(c).unwrap());
+                    assert_eq!(board.neighbors_mask(board.bit(coord).unwrap()), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_matches_board() {
+        // every size, filled in random orders, with the winner checked after each move
+        let mut rng = Lcg::new(2019);
+        for size in 1..=MAX_BITBOARD_SIZE {
+            for _ in 0..10 {
+                let cells = rng.shuffled_cells(size);
+                let mut board = Board::new(u16::from(size));
+                let mut bit_board = BitBoard::new(size);
+                for (i, &coord) in cells.iter().enumerate() {
+                    let color = if i % 2 == 0 { Color::Black } else { Color::White };
+                    board.place_piece(coord, color);
+                    bit_board.set(coord, color);
+                    assert_eq!(bit_board.status(), board.status());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_large() {
+        BitBoard::new(12);
+    }
+}
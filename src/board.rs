@@ -5,42 +5,145 @@
 //! Under the hood, this uses a union-find structure to keep track of the game status efficiently,
 //! and stores pieces in sets.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 use petgraph::unionfind::UnionFind;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::coord::Coord;
+pub use crate::cell::{Color, EmptyCellError, GameStatus, HexCell};
+use crate::coord::{Coord, Symmetry, ALPHABET};
+use crate::game::MoveError;
 
-/// One of the two possible colors in Hex.
+/// Which player connects which pair of edges.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Orientation {
+    /// The usual assignment: Black connects the left and right edges, and White connects the top
+    /// and bottom edges.
+    #[default]
+    Standard,
+    /// The reversed assignment: Black connects the top and bottom edges, and White connects the
+    /// left and right edges.
+    Reversed,
+}
+
+/// One of the two edges a player tries to connect.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Edge {
+    /// The left edge for Black, or the top edge for White.
+    First,
+    /// The right edge for Black, or the bottom edge for White.
+    Second,
+}
+
+/// The result of placing a piece with `Board::place_piece`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum Color {
-    /// The left-right player that goes first.
-    Black,
-    /// The top-bottom player that goes second.
-    White,
+pub struct PlaceResult {
+    /// Whether the piece was placed: false if the cell was occupied or out of bounds.
+    pub placed: bool,
+    /// The color of the piece if placing it won the game, and `None` otherwise, including when the
+    /// game had already been won.
+    pub won: Option<Color>,
+}
+
+/// How `Board::to_labeled_string_with` labels the rows and columns, to match sites that write
+/// coordinates differently. Only the labels change: the board is always drawn the same way. The
+/// default is this crate's own labeling, with lettered columns and numbered rows from the top left.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DisplayConfig {
+    /// Whether the rows are lettered and the columns numbered, instead of the other way around.
+    pub letters_on_rows: bool,
+    /// Whether the rows are counted up from the bottom row instead of down from the top row.
+    pub origin_bottom_left: bool,
+}
+
+/// What happened when placing a piece with `Board::place_piece_verbose`, for showing how a move
+/// joins groups together.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PlaceReport {
+    /// One piece from each separate group of the same color that the new piece joined, going
+    /// clockwise around the new piece from the top left. The edges aren't included, even though the
+    /// union-find treats them as groups.
+    pub merged: Vec<Coord>,
+    /// The color of the piece if placing it won the game, and `None` otherwise, as in `PlaceResult`.
+    pub won: Option<Color>,
 }
 
-/// A simple descriptor of the possible values at a Hex tile: black piece, white piece, or empty.
+/// The largest supported board size, limited by the letters available for coordinates.
+pub const MAX_SIZE: u16 = 26;
+
+/// An error for creating a board with the given size, which is either zero or larger than
+/// `MAX_SIZE`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum HexCell {
-    /// A Black piece.
-    Black,
-    /// A White piece.
-    White,
-    /// An empty cell.
-    Empty
+pub struct BoardSizeError(pub u16);
+
+impl fmt::Display for BoardSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid board size {}: must be between 1 and {}", self.0, MAX_SIZE)
+    }
 }
 
-/// A simple descriptor of the game status: ongoing, black victory, or white victory.
+impl error::Error for BoardSizeError {}
+
+/// An error for parsing a board from the diagram drawn by `Board::to_ascii`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum GameStatus {
-    /// Black wins.
-    BlackWin,
-    /// White wins.
-    WhiteWin,
-    /// The game does not yet have a winner.
-    Ongoing
+pub enum ParseBoardError {
+    /// The number of rows is zero or larger than `MAX_SIZE`.
+    InvalidSize(usize),
+    /// The row with the given index isn't indented by one more space than the row above it.
+    BadIndentation(usize),
+    /// The row with the given index doesn't have one cell for each row of the board.
+    WrongRowLength(usize),
+    /// The given character isn't `X`, `O`, or `.`, or for `Board::parse_loose`, one of the Unicode
+    /// cells drawn by `Display`.
+    InvalidCell(char),
+    /// The diagram has the given number of rows, instead of one for each row of the board.
+    WrongRowCount(usize),
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseBoardError::InvalidSize(rows) => write!(f, "invalid board diagram with {} rows", rows),
+            ParseBoardError::BadIndentation(row) => write!(f, "row {} of board diagram is indented wrongly", row),
+            ParseBoardError::WrongRowLength(row) => write!(f, "row {} of board diagram has the wrong length", row),
+            ParseBoardError::InvalidCell(c) => write!(f, "invalid cell {:?} in board diagram", c),
+            ParseBoardError::WrongRowCount(rows) => write!(f, "board diagram has the wrong number of rows: {}", rows),
+        }
+    }
+}
+
+impl error::Error for ParseBoardError {}
+
+/// The random keys used for Zobrist hashing, one for each color in each cell of the largest possible
+/// board, indexed by `y * MAX_SIZE + x`. These are generated at compile time from a fixed seed, so
+/// hashes are the same in every run.
+static ZOBRIST_KEYS: [[u64; 2]; (MAX_SIZE * MAX_SIZE) as usize] = zobrist_keys();
+
+/// Generates the Zobrist keys with the SplitMix64 generator.
+const fn zobrist_keys() -> [[u64; 2]; (MAX_SIZE * MAX_SIZE) as usize] {
+    let mut keys = [[0; 2]; (MAX_SIZE * MAX_SIZE) as usize];
+    let mut state: u64 = 0x4845_5847_414d_4521;
+    let mut i = 0;
+    while i < keys.len() {
+        let mut color = 0;
+        while color < 2 {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            keys[i][color] = z ^ (z >> 31);
+            color += 1;
+        }
+        i += 1;
+    }
+    keys
 }
 
 /// A Hex board of a given size, with pieces placed. The maximum size is 26, due to the limitations of
@@ -67,6 +170,40 @@ pub struct Board {
     white: HashSet<Coord>,
     /// The current status of the board: black win, white win, or ongoing.
     status: GameStatus,
+    /// The Zobrist hash of the pieces on the board, updated as pieces are placed and removed.
+    zobrist: u64,
+    /// Which player connects which pair of edges.
+    orientation: Orientation,
+    /// The most recent piece placed with `place_piece`, if it's still on the board.
+    last_move: Option<Coord>,
+    /// The piece placed with `place_piece` that connected the winner's edges, if it's known and
+    /// still on the board.
+    winning_move: Option<Coord>,
+}
+
+/// Boards are equal if they have the same size, orientation, and pieces in the same places. The
+/// union-find structures, last move, and winning move aren't compared: they can differ for the
+/// same position, depending on the order the pieces were placed in.
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.size == other.size && self.orientation == other.orientation &&
+            self.black == other.black && self.white == other.white
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        self.orientation.hash(state);
+        // sets have no order of their own, so hash the pieces in row-major order
+        for pieces in [&self.black, &self.white] {
+            let mut pieces: Vec<&Coord> = pieces.iter().collect();
+            pieces.sort();
+            pieces.hash(state);
+        }
+    }
 }
 
 impl Default for Board {
@@ -78,10 +215,184 @@ impl Default for Board {
 
 impl Board {
     /// Initializes a blank board with given size less than or equal to 26.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or larger than 26. Use `try_new` to handle invalid sizes instead.
     pub fn new(size: u16) -> Board {
+        Board::new_with_orientation(size, Orientation::Standard)
+    }
+    /// Initializes a blank board with the given size, or returns an error if the size is 0 or larger
+    /// than 26.
+    pub fn try_new(size: u16) -> Result<Board, BoardSizeError> {
+        if size == 0 || size > MAX_SIZE {
+            return Err(BoardSizeError(size));
+        }
+        Ok(Board::new(size))
+    }
+    /// Initializes a blank board with given size less than or equal to 26, on which each player
+    /// connects the edges given by the orientation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or larger than 26, like `new`.
+    pub fn new_with_orientation(size: u16, orientation: Orientation) -> Board {
+        // the coordinates only go up to z26, and past that the union-find's node numbers could
+        // overflow a u16
+        if size == 0 || size > MAX_SIZE {
+            panic!("{}", BoardSizeError(size));
+        }
+        let mut board = Board {
+            size,
+            black_unions: UnionFind::new(0),
+            white_unions: UnionFind::new(0),
+            black: HashSet::new(),
+            white: HashSet::new(),
+            status: GameStatus::Ongoing,
+            zobrist: 0,
+            orientation,
+            last_move: None,
+            winning_move: None,
+        };
+        board.black_unions = board.edge_unions(Color::Black);
+        board.white_unions = board.edge_unions(Color::White);
+        board
+    }
+    /// Returns which player connects which pair of edges.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+    /// Initializes a board of the given size with the given pieces already placed, as for a puzzle or
+    /// a handicap game. The status is that of the resulting position. Returns an error if any of the
+    /// pieces are out of bounds or in the same cell as another one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or larger than 26, like `new`. Use `try_new` to check the size first.
+    pub fn with_setup(size: u16, black: &[Coord], white: &[Coord]) -> Result<Board, MoveError> {
+        let mut board = Board::new(size);
+        let pieces = black.iter().map(|&c| (c, Color::Black)).chain(white.iter().map(|&c| (c, Color::White)));
+        for (coord, color) in pieces {
+            if !board.place_piece_deferred(coord, color) {
+                return Err(match board.piece(coord) {
+                    HexCell::Empty => MoveError::OutOfBounds,
+                    cell => MoveError::Occupied(cell),
+                });
+            }
+        }
+        board.refresh_status();
+        Ok(board)
+    }
+    /// Returns the width and height of the bounding box of this board when drawn with the given hex
+    /// width, using the layout from `Coord::to_pixel`.
+    pub fn bounding_box(&self, hex_size: f64) -> (f64, f64) {
+        let size = f64::from(self.size);
+        // the distance from the center of a hex to one of its corners
+        let radius = hex_size / 3f64.sqrt();
+        // the bottom row is shifted right by half a hex for each row above it
+        let width = hex_size * (size + (size - 1.0) / 2.0);
+        // rows overlap by half of a corner-to-corner hex height
+        let height = radius * (2.0 + 1.5 * (size - 1.0));
+        (width, height)
+    }
+    /// Renders this board as a standalone SVG image, using the layout from `Coord::to_pixel` with the
+    /// given hex width. Each hex is drawn as its own polygon, and each player's edges are outlined in
+    /// that player's color.
+    pub fn to_svg(&self, hex_size: f64) -> String {
+        self.svg(hex_size, false)
+    }
+    /// Renders this board as a standalone SVG image like `to_svg`, but with each empty hex labeled
+    /// with its coordinate.
+    pub fn to_labeled_svg(&self, hex_size: f64) -> String {
+        self.svg(hex_size, true)
+    }
+    /// Renders the board as SVG, optionally labeling the empty hexes.
+    fn svg(&self, hex_size: f64, labels: bool) -> String {
+        let (width, height) = self.bounding_box(hex_size);
+        let margin = hex_size / 4.0;
+        // offsets of each corner from the center of a pointy-top hex, clockwise from the top
+        let radius = hex_size / 3f64.sqrt();
+        let half = hex_size / 2.0;
+        let corners = [(0.0, -radius), (half, -radius / 2.0), (half, radius / 2.0),
+                       (0.0, radius), (-half, radius / 2.0), (-half, -radius / 2.0)];
+        let corner = |coord: Coord, i: usize| {
+            let (x, y) = coord.to_pixel(hex_size);
+            format!("{:.2},{:.2}", x + corners[i].0, y + corners[i].1)
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:.2} {:.2} {:.2} {:.2}\">\n",
+            -margin, -margin, width + 2.0 * margin, height + 2.0 * margin);
+        svg.push_str(&format!("<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"#d0d0d0\"/>\n",
+                              -margin, -margin, width + 2.0 * margin, height + 2.0 * margin));
+        for (coord, cell) in self.cells() {
+            let fill = match cell {
+                HexCell::Black => "#000000",
+                HexCell::White => "#ffffff",
+                HexCell::Empty => "#f0d9a0",
+            };
+            let points: Vec<String> = (0..6).map(|i| corner(coord, i)).collect();
+            svg.push_str(&format!("<polygon points=\"{}\" fill=\"{}\" stroke=\"#404040\"/>\n",
+                                  points.join(" "), fill));
+            if labels && cell == HexCell::Empty {
+                let (x, y) = coord.to_pixel(hex_size);
+                svg.push_str(&format!(
+                    "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{:.2}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    x, y, hex_size / 3.0, coord));
+            }
+        }
+
+        // each edge runs along the outer corners of the hexes on that side of the board
+        let size = self.size as u8;
+        let last = size - 1;
+        let mut top = vec![];
+        let mut bottom = vec![];
+        let mut left = vec![];
+        let mut right = vec![];
+        for i in 0..size {
+            top.push(corner(Coord{x: i, y: 0}, 5));
+            top.push(corner(Coord{x: i, y: 0}, 0));
+            bottom.push(corner(Coord{x: i, y: last}, 4));
+            bottom.push(corner(Coord{x: i, y: last}, 3));
+            left.push(corner(Coord{x: 0, y: i}, 5));
+            left.push(corner(Coord{x: 0, y: i}, 4));
+            right.push(corner(Coord{x: last, y: i}, 0));
+            right.push(corner(Coord{x: last, y: i}, 1));
+        }
+        top.push(corner(Coord{x: last, y: 0}, 1));
+        bottom.push(corner(Coord{x: last, y: last}, 2));
+        left.push(corner(Coord{x: 0, y: last}, 3));
+        right.push(corner(Coord{x: last, y: last}, 2));
+        let (horizontal, vertical) = match self.orientation {
+            Orientation::Standard => ("#ffffff", "#000000"),
+            Orientation::Reversed => ("#000000", "#ffffff"),
+        };
+        for (edge, color) in &[(top, horizontal), (bottom, horizontal), (left, vertical), (right, vertical)] {
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{:.2}\" stroke-linecap=\"round\"/>\n",
+                edge.join(" "), color, hex_size / 8.0));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+    /// Removes every piece from the board, returning it to the state of a new board of the same size.
+    /// The piece sets keep their allocated capacity, so this is cheaper than creating a new board
+    /// when playing many games in a row.
+    pub fn reset(&mut self) {
+        self.black.clear();
+        self.white.clear();
+        self.black_unions = self.edge_unions(Color::Black);
+        self.white_unions = self.edge_unions(Color::White);
+        self.status = GameStatus::Ongoing;
+        self.zobrist = 0;
+        self.last_move = None;
+        self.winning_move = None;
+    }
+    /// Returns a union-find for Black on a board of the given size with no pieces placed, in which
+    /// the virtual stones along each of the left and right edges are unioned together.
+    fn black_edge_unions(size: u16) -> UnionFind<u16> {
         // additional 2 rows and columns for virtual stones
         let mut black_unions = UnionFind::new(((size + 2) * (size + 2)) as usize);
-        let mut white_unions = UnionFind::new(((size + 2) * (size + 2)) as usize);
         // for each stone in the left and right columns, union together with the one above it
         for y in 1..=size {
             // corresponding to the left edge at the given height: (0, y) maps to (size + 2) * y
@@ -91,26 +402,69 @@ impl Board {
             // this connects (size - 1, y) and (size - 1, y - 1)
             black_unions.union((y + 1) * (size + 2) - 1, y * (size + 2) - 1);
         }
+        black_unions
+    }
+    /// Returns a union-find for White on a board of the given size with no pieces placed, in which
+    /// the virtual stones along each of the top and bottom edges are unioned together.
+    fn white_edge_unions(size: u16) -> UnionFind<u16> {
+        // additional 2 rows and columns for virtual stones
+        let mut white_unions = UnionFind::new(((size + 2) * (size + 2)) as usize);
         // for each stone in the top and bottom rows, union together with the one to the left of it
         for x in 1..=size {
             // corresponding to the top edge at the given x: (x, 0) maps to x
             // this connects (x, 0) and (x - 1, 0)
             white_unions.union(x, x - 1);
-            // the bottom edge: (x, size - 1) maps to (size - 1) * size + x
-            // this connects (x, size - 1) and (x - 1, size - 1)
-            white_unions.union((size - 1) * size + x, (size - 1) * size + x -  1);
+            // the bottom edge: (x, size + 1) maps to (size + 1) * (size + 2) + x
+            // this connects (x, size + 1) and (x - 1, size + 1)
+            white_unions.union((size + 1) * (size + 2) + x, (size + 1) * (size + 2) + x - 1);
         }
-        let black = HashSet::new();
-        let white = HashSet::new();
-        Board {
-            size,
-            black_unions,
-            white_unions,
-            black,
-            white,
-            status: GameStatus::Ongoing,
+        white_unions
+    }
+    /// Returns the color that connects the same edges as the given color does on a board with the
+    /// standard orientation: the color itself, or its opponent if the orientation is reversed.
+    /// Everything that depends on which edges a color connects goes through this.
+    fn standard_color(&self, color: Color) -> Color {
+        match self.orientation {
+            Orientation::Standard => color,
+            Orientation::Reversed => color.opponent(),
         }
     }
+    /// Returns a union-find for the given color with no pieces placed, in which the virtual stones
+    /// along each of the color's edges are unioned together.
+    fn edge_unions(&self, color: Color) -> UnionFind<u16> {
+        match self.standard_color(color) {
+            Color::Black => Board::black_edge_unions(self.size),
+            Color::White => Board::white_edge_unions(self.size),
+        }
+    }
+    /// Returns the numbers of two virtual stones, one on each of the given color's edges, which are
+    /// in the same group of that color's union-find exactly when the color has connected its edges.
+    fn edge_nums(&self, color: Color) -> (u16, u16) {
+        match self.standard_color(color) {
+            // the squares one below the top left and right corners
+            Color::Black => (self.size + 2, (self.size + 2) * 2 - 1),
+            // the squares one to the right of the top and bottom left corners
+            Color::White => (1, (self.size + 2) * (self.size + 1) + 1),
+        }
+    }
+    /// Builds the union-find for the given color from scratch, starting from the virtual edges and
+    /// unioning each of that color's pieces with its same-colored neighbors.
+    fn rebuild_unions(&self, color: Color) -> UnionFind<u16> {
+        let mut unions = self.edge_unions(color);
+        let (pieces, cell) = match color {
+            Color::Black => (&self.black, HexCell::Black),
+            Color::White => (&self.white, HexCell::White),
+        };
+        for coord in pieces {
+            let num = self.coord_to_num(*coord);
+            for neighbor in self.num_neighbors(num) {
+                if self.piece_at_num(neighbor) == cell {
+                    unions.union(num, neighbor);
+                }
+            }
+        }
+        unions
+    }
     /// Gets the integer value that maps to a given coordinate in this board size, reading in normal
     /// left-right top-down order. However, everything is shifted down and right by one, because there
     /// are virtual stones on the top and left edges. Basically, the size of the board is increased by
@@ -131,12 +485,15 @@ impl Board {
     }
     /// Determines whether the given number is black, white, or empty, including the virtual stones.
     fn piece_at_num(&self, num: u16) -> HexCell {
-        // anything divisible by the real size or one before that is on the left or right edge and is black
-        if num % (self.size + 2) == 0 || (num + 1) % (self.size + 2) == 0 {
-            HexCell::Black
-        } else if num <= self.size + 1 || num > (self.size + 1) * (self.size) {
-            // anything below size + 1, or above (size + 1) * (size), is white
-            HexCell::White
+        let row = num / (self.size + 2);
+        let col = num % (self.size + 2);
+        if col == 0 || col == self.size + 1 {
+            // the left and right columns, including the corners, belong to the left-right player:
+            // Black in the standard orientation
+            HexCell::from(self.standard_color(Color::Black))
+        } else if row == 0 || row == self.size + 1 {
+            // the rest of the top and bottom rows belong to the top-bottom player
+            HexCell::from(self.standard_color(Color::White))
         } else {
             // now num_to_coord is guaranteed to work, just test the board as normal
             self.piece(self.num_to_coord(num))
@@ -145,9 +502,9 @@ impl Board {
     /// Gets the six numbers corresponding to the neighbors of a given integer when mapped to
     /// coordinates, including virtual stones. Has undefined behavior for the top left edge and may
     /// panic.
-    fn num_neighbors(&self, num: u16) -> Vec<u16> {
+    fn num_neighbors(&self, num: u16) -> [u16; 6] {
         let size = self.size + 2; // to account for virtual stones
-        vec![
+        [
             num - size, // top left
             num - size + 1, // top right
             num + 1, // right
@@ -157,14 +514,62 @@ impl Board {
         ]        
     }
     /// Places the piece at the given spot if the placement is valid (there are no other pieces and
-    /// the coordinate is within range), modifying the board's state and returning true. Otherwise,
-    /// does not modify the board state and returns false.
-    pub fn place_piece(&mut self, coord: Coord, color: Color) -> bool {
+    /// the coordinate is within range), modifying the board's state. Otherwise, does not modify the
+    /// board state. The result says whether the piece was placed, and whether it won the game by
+    /// connecting that color's edges.
+    pub fn place_piece(&mut self, coord: Coord, color: Color) -> PlaceResult {
+        if !self.place_piece_deferred(coord, color) {
+            return PlaceResult { placed: false, won: None };
+        }
+        self.last_move = Some(coord);
+        // only the color that just moved can have connected its edges, and only if it hadn't
+        // already
+        let won = self.status == GameStatus::Ongoing && self.connects_edges(color);
+        if won {
+            self.status = match color {
+                Color::Black => GameStatus::BlackWin,
+                Color::White => GameStatus::WhiteWin,
+            };
+            self.winning_move = Some(coord);
+        }
+        debug_assert!(!self.is_full() || self.status != GameStatus::Ongoing,
+                      "a full board must have a winner");
+        PlaceResult { placed: true, won: if won { Some(color) } else { None } }
+    }
+    /// Places the piece like `place_piece`, but also reports which of the color's groups the piece
+    /// joined together. Returns `None` and does nothing if the piece can't be placed.
+    pub fn place_piece_verbose(&mut self, coord: Coord, color: Color) -> Option<PlaceReport> {
+        if u16::from(coord.x) >= self.size || u16::from(coord.y) >= self.size ||
+            self.piece(coord) != HexCell::Empty {
+            return None;
+        }
+        let unions = match color {
+            Color::Black => &self.black_unions,
+            Color::White => &self.white_unions,
+        };
+        let mut roots = vec![];
+        let mut merged = vec![];
+        for neighbor in coord.neighbors_iter(self.size as u8) {
+            if self.piece(neighbor) == HexCell::from(color) {
+                let root = unions.find(self.coord_to_num(neighbor));
+                if !roots.contains(&root) {
+                    roots.push(root);
+                    merged.push(neighbor);
+                }
+            }
+        }
+        let result = self.place_piece(coord, color);
+        Some(PlaceReport { merged, won: result.won })
+    }
+    /// Places the piece like `place_piece`, returning whether it was placed, but without checking
+    /// whether it won: the status is left as it was until `refresh_status` is called. This saves
+    /// the check when placing many pieces at once.
+    pub(crate) fn place_piece_deferred(&mut self, coord: Coord, color: Color) -> bool {
         if u16::from(coord.x) >= self.size ||
             u16::from(coord.y) >= self.size ||
             self.piece(coord) != HexCell::Empty {
-                // if out of bounds, return false and do nothing
-                // if existing piece, return false and do nothing
+                // if out of bounds, do nothing
+                // if existing piece, do nothing
             false
         } else {
             let num = self.coord_to_num(coord);
@@ -194,11 +599,79 @@ impl Board {
                     }
                 }
             }
-            // update game status
-            self.set_game_status();
+            self.zobrist ^= Board::zobrist_key(coord, color);
             true
         }        
     }
+    /// Recomputes the status from scratch after placing pieces with `place_piece_deferred`,
+    /// returning the new status.
+    pub(crate) fn refresh_status(&mut self) -> GameStatus {
+        self.set_game_status()
+    }
+    /// Removes the piece at the given spot if there is one, modifying the board's state and returning
+    /// true. Otherwise, does not modify the board state and returns false. The union-find structure
+    /// can't undo unions, so the removed piece's color has its connectivity rebuilt from the
+    /// remaining pieces: this is much slower than placing a piece.
+    pub fn remove_piece(&mut self, coord: Coord) -> bool {
+        if self.last_move == Some(coord) {
+            self.last_move = None;
+        }
+        // removing any of the winner's pieces could change which chain connects their edges
+        let winner = match self.status {
+            GameStatus::BlackWin => HexCell::Black,
+            GameStatus::WhiteWin => HexCell::White,
+            GameStatus::Ongoing => HexCell::Empty,
+        };
+        if self.piece(coord) == winner {
+            self.winning_move = None;
+        }
+        match self.piece(coord) {
+            HexCell::Empty => false,
+            HexCell::Black => {
+                self.black.remove(&coord);
+                self.zobrist ^= Board::zobrist_key(coord, Color::Black);
+                self.black_unions = self.rebuild_unions(Color::Black);
+                self.set_game_status();
+                true
+            }
+            HexCell::White => {
+                self.white.remove(&coord);
+                self.zobrist ^= Board::zobrist_key(coord, Color::White);
+                self.white_unions = self.rebuild_unions(Color::White);
+                self.set_game_status();
+                true
+            }
+        }
+    }
+    /// Returns the most recent piece placed with `place_piece`, so that it can be highlighted, or
+    /// `None` if no pieces have been placed since the board was created or reset, or that piece was
+    /// removed. Pieces placed by `with_setup` don't count as moves.
+    pub fn last_move(&self) -> Option<Coord> {
+        self.last_move
+    }
+    /// Returns the piece that won the game by connecting its color's edges, so that it can be
+    /// highlighted along with `winning_path`, or `None` if no one has won. This is also `None` if
+    /// the win came from pieces placed all at once, as with `with_setup`, or if any of the
+    /// winner's pieces have been removed since.
+    pub fn winning_move(&self) -> Option<Coord> {
+        self.winning_move
+    }
+    /// Returns the Zobrist key for a piece of the given color at the given coordinate.
+    fn zobrist_key(coord: Coord, color: Color) -> u64 {
+        let index = usize::from(coord.y) * usize::from(MAX_SIZE) + usize::from(coord.x);
+        match color {
+            Color::Black => ZOBRIST_KEYS[index][0],
+            Color::White => ZOBRIST_KEYS[index][1],
+        }
+    }
+    /// Returns the Zobrist hash of the pieces on the board: the exclusive or of a fixed random key
+    /// for each piece. This is kept up to date as pieces are placed and removed, so unlike `Hash`,
+    /// getting it takes constant time, which makes it a good key for transposition tables. Equal
+    /// positions on boards of the same size always have the same hash; the size itself isn't
+    /// included.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist
+    }
     /// Returns a `HexCell` value describing the piece at the given location: `Empty` if no piece is
     /// there, `Black` if Black has a piece, or `White` if White has a piece. If the coordinate is out
     /// of bounds, returns `Empty`.
@@ -211,18 +684,340 @@ impl Board {
             HexCell::Empty
         }
     }
+    /// Returns an iterator over every coordinate on the board along with its contents, in row-major
+    /// order: left to right along the top row, then the next row, and so on.
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, HexCell)> + '_ {
+        (0..self.size).flat_map(move |y| {
+            (0..self.size).map(move |x| {
+                let coord = Coord{x: x as u8, y: y as u8};
+                (coord, self.piece(coord))
+            })
+        })
+    }
+    /// Returns each of the given cell's neighbors that lie on this board, clockwise from the top
+    /// left, like `Coord::neighbors_within` with this board's size.
+    pub fn neighbors(&self, coord: Coord) -> Vec<Coord> {
+        coord.neighbors_within(self.size as u8)
+    }
+    /// Returns each of the given cell's neighbors that lie on this board and are empty, clockwise from
+    /// the top left.
+    pub fn empty_neighbors(&self, coord: Coord) -> Vec<Coord> {
+        coord.neighbors_iter(self.size as u8)
+            .filter(|&neighbor| self.piece(neighbor) == HexCell::Empty)
+            .collect()
+    }
+    /// Returns every cell whose contents differ between this board and the other, as the
+    /// coordinate, this board's contents, and the other board's contents, in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the boards have different sizes.
+    pub fn diff(&self, other: &Board) -> Vec<(Coord, HexCell, HexCell)> {
+        assert_eq!(self.size, other.size, "can't compare boards of different sizes");
+        self.cells()
+            .map(|(coord, old)| (coord, old, other.piece(coord)))
+            .filter(|&(_, old, new)| old != new)
+            .collect()
+    }
+    /// Returns an iterator over the coordinates of every piece of the given color, in no particular
+    /// order.
+    pub fn pieces(&self, color: Color) -> impl Iterator<Item = Coord> + '_ {
+        match color {
+            Color::Black => self.black.iter().cloned(),
+            Color::White => self.white.iter().cloned(),
+        }
+    }
+    /// Returns the position given by applying the symmetry to this board. If the symmetry is a
+    /// reflection, which exchanges the edges belonging to each player, every piece also changes
+    /// color, so that a win for one player becomes a win for the other. Otherwise, colors and the
+    /// winner are preserved.
+    pub fn transform(&self, symmetry: Symmetry) -> Board {
+        let size = self.size as u8;
+        let (black, white) = if symmetry.swaps_colors() {
+            (&self.white, &self.black)
+        } else {
+            (&self.black, &self.white)
+        };
+        let mut board = Board::new_with_orientation(self.size, self.orientation);
+        for coord in black {
+            board.place_piece(coord.transform(symmetry, size), Color::Black);
+        }
+        for coord in white {
+            board.place_piece(coord.transform(symmetry, size), Color::White);
+        }
+        board.last_move = self.last_move.map(|coord| coord.transform(symmetry, size));
+        board.winning_move = self.winning_move.map(|coord| coord.transform(symmetry, size));
+        board
+    }
+    /// Returns this position rotated 180 degrees, which preserves the colors of every piece.
+    pub fn rotate_180(&self) -> Board {
+        self.transform(Symmetry::Rotate180)
+    }
+    /// Returns this position reflected across the long diagonal, with the colors of every piece
+    /// swapped.
+    pub fn reflect_long_diagonal(&self) -> Board {
+        self.transform(Symmetry::ReflectLongDiagonal)
+    }
+    /// Returns a canonical form of this position that is the same for the position and its 180-degree
+    /// rotation, which are equivalent. Of the two, this chooses whichever comes first when their
+    /// cells are compared in row-major order, with Black before White before empty. The reflections
+    /// aren't considered, because they swap the colors and so aren't the same position.
+    pub fn canonical(&self) -> Board {
+        let key = |board: &Board| -> Vec<u8> {
+            board.cells().map(|(_, cell)| match cell {
+                HexCell::Black => 0,
+                HexCell::White => 1,
+                HexCell::Empty => 2,
+            }).collect()
+        };
+        let rotated = self.rotate_180();
+        if key(&rotated) < key(self) {
+            rotated
+        } else {
+            self.clone()
+        }
+    }
+    /// Applies the swap rule to the only piece on the board, reflecting it across the long diagonal
+    /// and changing its color, and returns `true`. This is what `Game::swap` does to Black's opening
+    /// stone, and applying it again undoes it. If there isn't exactly one piece on the board, returns
+    /// `false` and does nothing.
+    pub fn apply_swap(&mut self) -> bool {
+        let (coord, color) = match (self.black.iter().next(), self.white.iter().next()) {
+            (Some(&coord), None) if self.black.len() == 1 => (coord, Color::Black),
+            (None, Some(&coord)) if self.white.len() == 1 => (coord, Color::White),
+            _ => return false,
+        };
+        self.remove_piece(coord);
+        self.place_piece(coord.reflect_long_diagonal(self.size as u8), color.opponent());
+        true
+    }
+    /// Returns the number of pieces of the given color on the board.
+    pub fn piece_count(&self, color: Color) -> usize {
+        match color {
+            Color::Black => self.black.len(),
+            Color::White => self.white.len(),
+        }
+    }
+    /// Returns the number of empty cells on the board.
+    pub fn empty_count(&self) -> usize {
+        usize::from(self.size) * usize::from(self.size) - self.black.len() - self.white.len()
+    }
+    /// Encodes the board as three planes of `size * size` values each, for use as the input of a
+    /// neural network: the first plane is 1 where Black has a piece, the second is 1 where White
+    /// has a piece, and the third is 1 everywhere if `to_move` is Black and 0 everywhere if it's
+    /// White. Every other value is 0. Each plane is in row-major order, and the planes are
+    /// concatenated.
+    pub fn to_planes(&self, to_move: Color) -> Vec<f32> {
+        let cells = usize::from(self.size) * usize::from(self.size);
+        let mut planes = vec![0.0; 3 * cells];
+        for (i, (_, cell)) in self.cells().enumerate() {
+            match cell {
+                HexCell::Black => planes[i] = 1.0,
+                HexCell::White => planes[cells + i] = 1.0,
+                HexCell::Empty => {}
+            }
+        }
+        if to_move == Color::Black {
+            for value in &mut planes[2 * cells..] {
+                *value = 1.0;
+            }
+        }
+        planes
+    }
+    /// Returns whether every cell on the board is occupied. Hex can't end in a draw, so a full board
+    /// always has a winner.
+    pub fn is_full(&self) -> bool {
+        self.empty_count() == 0
+    }
+    /// Draws the board as text, using the given characters for black pieces, white pieces, and empty
+    /// cells. Each row is indented one more space than the last, making the parallelogram shape.
+    fn diagram(&self, black: char, white: char, empty: char) -> String {
+        let mut s = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                s.push(match self.piece(Coord{x: x as u8, y: y as u8}) {
+                    HexCell::Black => black,
+                    HexCell::White => white,
+                    HexCell::Empty => empty,
+                });
+                // push a space, so that the next row can fit in between these pieces
+                s.push(' ');
+            }
+            // separate with a newline and the right number of spaces
+            s.push('\n');
+            for _ in 0..=y {
+                s.push(' ');
+            }
+        }
+        s
+    }
+    /// Draws the board like its `Display` implementation, but using only ASCII characters: `X` for
+    /// Black, `O` for White, and `.` for empty cells. This is useful for terminals and logs that
+    /// can't show the Unicode hexagons.
+    pub fn to_ascii(&self) -> String {
+        self.diagram('X', 'O', '.')
+    }
+    /// Reads a board of the given size from a diagram like the ones drawn by `to_ascii` or `Display`,
+    /// but without requiring any particular layout, as for diagrams pasted from forums. Whitespace
+    /// anywhere in a row is ignored, as are blank lines, and each of the other lines must have
+    /// exactly `size` cells. Cells can be `X`, `O`, and `.`, or `⬢`, `⬡`, and `⋅`, mixed freely.
+    pub fn parse_loose(s: &str, size: u16) -> Result<Board, ParseBoardError> {
+        if size == 0 || size > MAX_SIZE {
+            return Err(ParseBoardError::InvalidSize(usize::from(size)));
+        }
+        let rows: Vec<&str> = s.lines().filter(|line| !line.trim().is_empty()).collect();
+        if rows.len() != usize::from(size) {
+            return Err(ParseBoardError::WrongRowCount(rows.len()));
+        }
+        let (mut black, mut white) = (vec![], vec![]);
+        for (y, row) in rows.iter().enumerate() {
+            let cells: Vec<char> = row.chars().filter(|c| !c.is_whitespace()).collect();
+            if cells.len() != usize::from(size) {
+                return Err(ParseBoardError::WrongRowLength(y));
+            }
+            for (x, &c) in cells.iter().enumerate() {
+                let coord = Coord{x: x as u8, y: y as u8};
+                match c {
+                    'X' | '⬢' => black.push(coord),
+                    'O' | '⬡' => white.push(coord),
+                    '.' | '⋅' => {}
+                    _ => return Err(ParseBoardError::InvalidCell(c)),
+                }
+            }
+        }
+        // every piece is in its own cell on the board, so this can't fail
+        Ok(Board::with_setup(size, &black, &white).unwrap())
+    }
+    /// Draws the board like its `Display` implementation, but with the column letters printed above
+    /// the board and the row numbers printed to the left of each row, so that cells can be found by
+    /// their coordinates.
+    pub fn to_labeled_string(&self) -> String {
+        self.to_labeled_string_with(DisplayConfig::default())
+    }
+    /// Draws the board like `to_labeled_string`, but with the rows and columns labeled as the
+    /// configuration says. Numbers above the board that have more than one digit are written
+    /// downwards, one digit per line, so that each still lines up with its column.
+    pub fn to_labeled_string_with(&self, config: DisplayConfig) -> String {
+        let label = |i: u16, letter: bool| -> String {
+            if letter {
+                ALPHABET.chars().nth(usize::from(i)).unwrap().to_string()
+            } else {
+                (i + 1).to_string()
+            }
+        };
+        let row_label = |y: u16| {
+            let row = if config.origin_bottom_left { self.size - 1 - y } else { y };
+            label(row, config.letters_on_rows)
+        };
+        let columns: Vec<String> = (0..self.size).map(|x| label(x, !config.letters_on_rows)).collect();
+        // row labels are right-aligned, so every row starts at the same place
+        let width = (0..self.size).map(|y| row_label(y).len()).max().unwrap();
+        let height = columns.iter().map(String::len).max().unwrap();
+        let mut s = String::new();
+        for line in 0..height {
+            s.push_str(&" ".repeat(width + 1));
+            let digits: Vec<String> = columns.iter().map(|column| {
+                format!("{:>height$}", column, height = height).chars().nth(line).unwrap().to_string()
+            }).collect();
+            s.push_str(digits.join(" ").trim_end());
+            s.push('\n');
+        }
+        for y in 0..self.size {
+            s.push_str(&format!("{:>width$} ", row_label(y), width = width));
+            // shift each row over by one more space to keep the parallelogram shape
+            s.push_str(&" ".repeat(y as usize));
+            let cells: Vec<String> = (0..self.size).map(|x| {
+                match self.piece(Coord{x: x as u8, y: y as u8}) {
+                    HexCell::Black => "⬢",
+                    HexCell::White => "⬡",
+                    HexCell::Empty => "⋅",
+                }.to_string()
+            }).collect();
+            s.push_str(&cells.join(" "));
+            s.push('\n');
+        }
+        s
+    }
+    /// Returns the connected groups of the given color's pieces, where each group is every piece that
+    /// can be reached from any of the others through neighboring pieces of the same color. Unlike
+    /// `are_connected`, the edges don't join groups together, so these are the groups a player
+    /// would see on the board. Each group is in row-major order, and the groups are in row-major
+    /// order of their first pieces.
+    pub fn groups(&self, color: Color) -> Vec<Vec<Coord>> {
+        let cell = HexCell::from(color);
+        let mut pieces: Vec<Coord> = self.pieces(color).collect();
+        pieces.sort();
+        let mut seen: HashSet<Coord> = HashSet::new();
+        let mut groups = vec![];
+        for piece in pieces {
+            if !seen.insert(piece) {
+                continue;
+            }
+            // flood fill out from the first piece of each new group
+            let mut group = vec![piece];
+            let mut queue = VecDeque::new();
+            queue.push_back(piece);
+            while let Some(coord) = queue.pop_front() {
+                for neighbor in self.neighbors(coord) {
+                    if self.piece(neighbor) == cell && seen.insert(neighbor) {
+                        group.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            group.sort();
+            groups.push(group);
+        }
+        groups
+    }
+    /// Returns whether the two coordinates both have pieces of the given color, and those pieces are
+    /// connected by a chain of that color's pieces. This is a quick lookup in the union-find
+    /// structure that tracks the game status, so the color's own edges count as part of the chain:
+    /// two pieces touching the same edge are always connected.
+    pub fn are_connected(&self, a: Coord, b: Coord, color: Color) -> bool {
+        let (unions, cell) = match color {
+            Color::Black => (&self.black_unions, HexCell::Black),
+            Color::White => (&self.white_unions, HexCell::White),
+        };
+        self.piece(a) == cell && self.piece(b) == cell &&
+            unions.find(self.coord_to_num(a)) == unions.find(self.coord_to_num(b))
+    }
+    /// Returns whether the given color's pieces connect its two edges, according to its union-find.
+    fn connects_edges(&self, color: Color) -> bool {
+        let (first, second) = self.edge_nums(color);
+        let unions = match color {
+            Color::Black => &self.black_unions,
+            Color::White => &self.white_unions,
+        };
+        unions.find(first) == unions.find(second)
+    }
+    /// Returns whether playing the given color at the given empty cell would connect that color's
+    /// edges, found from the groups of its same-colored neighbors without placing the piece. Returns
+    /// false if the cell is occupied or out of bounds.
+    pub(crate) fn completes_connection(&self, coord: Coord, color: Color) -> bool {
+        if u16::from(coord.x) >= self.size || u16::from(coord.y) >= self.size ||
+            self.piece(coord) != HexCell::Empty {
+            return false;
+        }
+        let (unions, cell) = match color {
+            Color::Black => (&self.black_unions, HexCell::Black),
+            Color::White => (&self.white_unions, HexCell::White),
+        };
+        let (first, second) = self.edge_nums(color);
+        let groups: Vec<u16> = self.num_neighbors(self.coord_to_num(coord)).iter()
+            .filter(|&&neighbor| self.piece_at_num(neighbor) == cell)
+            .map(|&neighbor| unions.find(neighbor))
+            .collect();
+        groups.contains(&unions.find(first)) && groups.contains(&unions.find(second))
+    }
     /// Checks for a winner, updating the game status if a change is required and returning whatever
     /// the game status is.
     fn set_game_status(&mut self) -> GameStatus {
-        // if the squares one below the top left and right corners are equivalent, black has won,
-        // because the left and right are connected
-        if self.black_unions.find(self.size + 3) == self.black_unions.find((self.size + 2) * 2 - 1) {
+        if self.connects_edges(Color::Black) {
             self.status = GameStatus::BlackWin;
             GameStatus::BlackWin        
         }
-        // if the squares one to the right of the top and bottom left corners are connected, white
-        // has won because the top and bottom are connected
-        else if self.white_unions.find(1) == self.white_unions.find((self.size + 2) * (self.size + 1) + 1) {
+        else if self.connects_edges(Color::White) {
             self.status = GameStatus::WhiteWin;
             GameStatus::WhiteWin
         } else {
@@ -236,34 +1031,471 @@ impl Board {
     pub fn status(&self) -> GameStatus {
         self.status
     }
+    /// Returns whether the coordinate is on the first of the given color's edges: the left edge for
+    /// Black, or the top edge for White, in the standard orientation.
+    fn on_first_edge(&self, color: Color, coord: Coord) -> bool {
+        match self.standard_color(color) {
+            Color::Black => coord.x == 0,
+            Color::White => coord.y == 0,
+        }
+    }
+    /// Returns whether the coordinate is on the second of the given color's edges: the right edge for
+    /// Black, or the bottom edge for White, in the standard orientation.
+    fn on_second_edge(&self, color: Color, coord: Coord) -> bool {
+        match self.standard_color(color) {
+            Color::Black => u16::from(coord.x) == self.size - 1,
+            Color::White => u16::from(coord.y) == self.size - 1,
+        }
+    }
+    /// Returns the coordinates of the cells along one of the given color's edges, in order from the
+    /// top along the left and right edges or from the left along the top and bottom edges.
+    pub fn edge(&self, color: Color, which: Edge) -> impl Iterator<Item = Coord> {
+        let size = self.size as u8;
+        let line = match which {
+            Edge::First => 0,
+            Edge::Second => size - 1,
+        };
+        let standard_color = self.standard_color(color);
+        (0..size).map(move |i| match standard_color {
+            Color::Black => Coord{x: line, y: i},
+            Color::White => Coord{x: i, y: line},
+        })
+    }
+    /// Returns the cells on the long diagonal, where `x == y`, from the top left corner to the bottom
+    /// right. Reflecting across this diagonal is what the swap rule does to Black's opening stone.
+    pub fn long_diagonal(&self) -> Vec<Coord> {
+        (0..self.size as u8).map(|i| Coord{x: i, y: i}).collect()
+    }
+    /// Returns the cells on the short diagonal, where `x + y == size - 1`, from the top right corner
+    /// to the bottom left.
+    pub fn short_diagonal(&self) -> Vec<Coord> {
+        let last = self.size as u8 - 1;
+        (0..=last).map(|i| Coord{x: last - i, y: i}).collect()
+    }
+    /// If either player has won, returns one chain of the winner's pieces connecting their two
+    /// edges, ordered from the first edge (left for Black, top for White) to the second. Returns
+    /// `None` if the game is still ongoing. Because this is found by breadth-first search, the chain
+    /// is as short as possible, but there may be other chains that are just as short.
+    pub fn winning_path(&self) -> Option<Vec<Coord>> {
+        let (color, cell) = match self.status {
+            GameStatus::BlackWin => (Color::Black, HexCell::Black),
+            GameStatus::WhiteWin => (Color::White, HexCell::White),
+            GameStatus::Ongoing => return None,
+        };
+        // maps each piece reached to the piece it was reached from, or None on the first edge
+        let mut parents: HashMap<Coord, Option<Coord>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for (coord, _) in self.cells().filter(|&(c, piece)| piece == cell && self.on_first_edge(color, c)) {
+            parents.insert(coord, None);
+            queue.push_back(coord);
+        }
+        while let Some(coord) = queue.pop_front() {
+            if self.on_second_edge(color, coord) {
+                // walk back to the first edge
+                let mut path = vec![coord];
+                while let Some(&Some(parent)) = parents.get(path.last().unwrap()) {
+                    path.push(parent);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for neighbor in self.neighbors(coord) {
+                if self.piece(neighbor) == cell && !parents.contains_key(&neighbor) {
+                    parents.insert(neighbor, Some(coord));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+    /// Returns the empty cells that are dead: cells that can't be part of either player's winning
+    /// chain, so that it doesn't matter who fills them. Solvers can skip these moves entirely.
+    ///
+    /// This only finds cells that are dead because of their immediate neighborhood. A cell is
+    /// useless to a player if every neighbor it could connect to (each of that player's pieces and
+    /// edges, and each empty cell) is in or next to a single one of that player's connected groups,
+    /// or if there are at most two such neighbors and they're next to each other: any chain through
+    /// the cell can then go around it instead. A cell that's useless to both players is dead. For
+    /// example, a cell with four of one color's pieces in a row around it, or with one color's
+    /// pieces on one side and the other's on the other, is dead.
+    pub fn dead_cells(&self) -> HashSet<Coord> {
+        self.cells()
+            .filter(|&(coord, cell)| {
+                cell == HexCell::Empty && self.is_useless(coord, Color::Black) && self.is_useless(coord, Color::White)
+            })
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+    /// Returns whether an empty cell can't help the given color connect, judging only from its six
+    /// neighbors: see `dead_cells`.
+    fn is_useless(&self, coord: Coord, color: Color) -> bool {
+        let (unions, own) = match color {
+            Color::Black => (&self.black_unions, HexCell::Black),
+            Color::White => (&self.white_unions, HexCell::White),
+        };
+        let (first, second) = self.edge_nums(color);
+        // each neighbor, clockwise, is either part of one of the color's groups (named by its
+        // representative in the union-find), empty, or unusable, which is None
+        let neighbors: Vec<Option<Option<u16>>> = self.num_neighbors(self.coord_to_num(coord))
+            .iter()
+            .map(|&num| {
+                let (row, col) = (num / (self.size + 2), num % (self.size + 2));
+                // a corner off the board is on both colors' edges
+                let (row_out, col_out) = (row == 0 || row == self.size + 1, col == 0 || col == self.size + 1);
+                match self.standard_color(color) {
+                    Color::Black if col_out => Some(Some(unions.find(if col == 0 { first } else { second }))),
+                    Color::White if row_out => Some(Some(unions.find(if row == 0 { first } else { second }))),
+                    _ if row_out || col_out => None,
+                    _ => match self.piece_at_num(num) {
+                        HexCell::Empty => Some(None),
+                        cell if cell == own => Some(Some(unions.find(num))),
+                        _ => None,
+                    },
+                }
+            })
+            .collect();
+        let usable: Vec<usize> = (0..6).filter(|&i| neighbors[i].is_some()).collect();
+        // neighbors next to each other in clockwise order are next to each other on the board
+        let adjacent = |i: usize, j: usize| (i + 1) % 6 == j || (j + 1) % 6 == i;
+        match usable[..] {
+            [] | [_] => return true,
+            [i, j] if adjacent(i, j) => return true,
+            _ => {}
+        }
+        let groups: Vec<u16> = neighbors.iter().filter_map(|&n| n.flatten()).collect();
+        groups.iter().any(|&group| {
+            let in_group = |i: usize| neighbors[i] == Some(Some(group));
+            usable.iter().all(|&i| in_group(i) || in_group((i + 1) % 6) || in_group((i + 5) % 6))
+        })
+    }
+    /// Returns every bridge between two of the given color's pieces: two pieces that aren't adjacent
+    /// but share two neighbors, both of them empty. If the opponent plays in one of those two
+    /// "carrier" cells, the other one still connects the pieces, so a bridge is as good as a
+    /// connection. Each bridge is given as the two pieces, in row-major order, followed by the two
+    /// carrier cells, also in row-major order. Bridges are listed in row-major order of their first
+    /// piece.
+    pub fn bridges(&self, color: Color) -> Vec<(Coord, Coord, [Coord; 2])> {
+        // in cube coordinates, the pieces of a bridge differ by 2 in one component and -1 in the
+        // other two; only half of these point forwards in row-major order, so each bridge is only
+        // found once
+        const BRIDGE_OFFSETS: [(i16, i16, i16); 3] = [(2, -1, -1), (-1, 2, -1), (1, 1, -2)];
+        let own = HexCell::from(color);
+        let mut pieces: Vec<Coord> = self.pieces(color).collect();
+        pieces.sort();
+        let mut bridges = vec![];
+        for &piece in &pieces {
+            let (q, r, s) = piece.to_cube();
+            for &(dq, dr, ds) in &BRIDGE_OFFSETS {
+                let other = match Coord::from_cube(q + dq, r + dr, s + ds) {
+                    Some(c) if self.piece(c) == own => c,
+                    _ => continue,
+                };
+                // the carriers are the neighbors the two pieces have in common
+                let carriers: Vec<Coord> = self.neighbors(piece)
+                    .into_iter()
+                    .filter(|c| c.is_neighbor(other))
+                    .collect();
+                if carriers.len() == 2 && carriers.iter().all(|&c| self.piece(c) == HexCell::Empty) {
+                    let mut carriers = [carriers[0], carriers[1]];
+                    carriers.sort();
+                    bridges.push((piece, other, carriers));
+                }
+            }
+        }
+        bridges
+    }
+    /// Evaluates how well the given color's edges are connected by treating the board as an
+    /// electrical circuit, a classic Hex heuristic: the color's own pieces are wires, empty cells are
+    /// unit resistors, and the opponent's pieces don't conduct at all. A current flowing between two
+    /// neighboring cells passes through the resistance of both. Returns the conductance between the
+    /// two edges, which is higher the more (and shorter) ways there are to connect them: it's
+    /// infinite if the color has already won, and 0 if the opponent has cut the edges off.
+    pub fn resistance_score(&self, color: Color) -> f64 {
+        let (unions, own, opponent) = match color {
+            Color::Black => (&self.black_unions, HexCell::Black, HexCell::White),
+            Color::White => (&self.white_unions, HexCell::White, HexCell::Black),
+        };
+        let (first, second) = self.edge_nums(color);
+        // connected pieces all have the same voltage, so each group of pieces (including the edges)
+        // is a single node, named by its representative in the union-find
+        let source = unions.find(first);
+        let sink = unions.find(second);
+        if source == sink {
+            return f64::INFINITY;
+        }
+        // the conductance between each pair of neighboring nodes; every connection goes through at
+        // least one empty cell, since the pieces and edges they touch are already grouped together
+        let mut edges: HashMap<u16, Vec<(u16, f64)>> = HashMap::new();
+        for (coord, _) in self.cells().filter(|&(_, cell)| cell == HexCell::Empty) {
+            let num = self.coord_to_num(coord);
+            // a cell touching a group or edge on more than one side only connects to it once
+            let mut touched = vec![];
+            for neighbor in self.num_neighbors(num) {
+                let neighbor_cell = self.piece_at_num(neighbor);
+                let node = unions.find(neighbor);
+                if neighbor_cell == opponent || touched.contains(&node) {
+                    continue;
+                }
+                let conductance = if neighbor_cell == own {
+                    1.0
+                } else if neighbor < num {
+                    // pairs of empty cells are seen twice, so only count them from the lower one
+                    continue;
+                } else {
+                    // current passes through both empty cells
+                    0.5
+                };
+                touched.push(node);
+                edges.entry(num).or_default().push((node, conductance));
+                edges.entry(node).or_default().push((num, conductance));
+            }
+        }
+        // only solve for the nodes the current can actually reach
+        let mut indices: HashMap<u16, usize> = HashMap::new();
+        let mut seen: HashSet<u16> = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(source);
+        queue.push_back(source);
+        while let Some(node) = queue.pop_front() {
+            for &(next, _) in edges.get(&node).into_iter().flatten() {
+                if seen.insert(next) {
+                    if next != sink {
+                        indices.insert(next, indices.len());
+                    }
+                    queue.push_back(next);
+                }
+            }
+        }
+        if !seen.contains(&sink) {
+            return 0.0;
+        }
+        // with the source at voltage 1 and the sink at voltage 0, Kirchhoff's current law gives a
+        // linear equation for the voltage at every other node
+        let n = indices.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        let mut rhs = vec![0.0; n];
+        for (node, &i) in &indices {
+            for &(next, conductance) in &edges[node] {
+                matrix[i][i] += conductance;
+                if next == source {
+                    rhs[i] += conductance;
+                } else if let Some(&j) = indices.get(&next) {
+                    matrix[i][j] -= conductance;
+                }
+            }
+        }
+        let voltages = solve_linear_system(matrix, rhs);
+        // the total current out of the source, at a voltage of 1, is the conductance
+        edges[&source].iter().map(|&(next, conductance)| {
+            let voltage = indices.get(&next).map_or(0.0, |&j| voltages[j]);
+            conductance * (1.0 - voltage)
+        }).sum()
+    }
+    /// Returns the smallest number of pieces the given color still needs to place to connect its
+    /// two edges, or `None` if the opponent has already cut the edges off from each other. This is
+    /// `Some(0)` if the color has already won. This is the basis of most Hex heuristics.
+    ///
+    /// This is the cheapest chain from the color's first edge, found by `edge_distances`, to any
+    /// cell on the second edge.
+    pub fn connection_distance(&self, color: Color) -> Option<u16> {
+        let distances = self.edge_distances(color, Edge::First);
+        self.cells()
+            .zip(distances)
+            .filter(|&((coord, _), _)| self.on_second_edge(color, coord))
+            .map(|(_, distance)| distance)
+            .min()
+            .filter(|&distance| distance != u16::MAX)
+    }
+    /// Estimates the chance that the given color wins from this position, between 0 and 1, for
+    /// showing who's ahead. This squashes the difference between the players' connection distances,
+    /// as in `evaluate_batch`, through a logistic function, so that equal distances give 0.5 and every
+    /// cell of lead moves the estimate further towards 0 or 1. A color that has won or whose opponent
+    /// has been cut off gets exactly 1, and the opponent exactly 0. This is only a heuristic, and
+    /// doesn't account for whose turn it is.
+    pub fn win_probability(&self, color: Color) -> f64 {
+        // how quickly the estimate approaches certainty: a lead of one cell gives about 0.62
+        const SCALE: f64 = 0.5;
+        1.0 / (1.0 + (-SCALE * distance_score(self, color)).exp())
+    }
+    /// Returns, for each cell in row-major order, the number of empty cells in the cheapest chain
+    /// connecting the given color's two edges through that cell, counting the cell itself if it's
+    /// empty. This is `u16::MAX` for the opponent's pieces and for cells that can't be connected to
+    /// both edges. On an empty board, every cell is on a connection as short as the board is wide,
+    /// and the smallest value is always `connection_distance`.
+    pub fn edge_distance_map(&self, color: Color) -> Vec<u16> {
+        let first = self.edge_distances(color, Edge::First);
+        let second = self.edge_distances(color, Edge::Second);
+        self.cells().enumerate().map(|(i, (_, cell))| {
+            if first[i] == u16::MAX || second[i] == u16::MAX {
+                u16::MAX
+            } else if cell == HexCell::Empty {
+                // both paths count the cell itself
+                first[i] + second[i] - 1
+            } else {
+                first[i] + second[i]
+            }
+        }).collect()
+    }
+    /// Returns, for each cell in row-major order, the number of empty cells in the cheapest chain
+    /// from the given edge of the given color to that cell, counting the cell itself if it's empty,
+    /// or `u16::MAX` if there is no such chain. This is found with a 0-1 breadth-first search, in
+    /// which the color's own pieces cost nothing, empty cells cost one, and the opponent's pieces
+    /// can't be passed through.
+    fn edge_distances(&self, color: Color, from: Edge) -> Vec<u16> {
+        let size = self.size as u8;
+        let (own, opponent) = match color {
+            Color::Black => (HexCell::Black, HexCell::White),
+            Color::White => (HexCell::White, HexCell::Black),
+        };
+        let cost = |coord: Coord| -> Option<u16> {
+            match self.piece(coord) {
+                cell if cell == own => Some(0),
+                cell if cell == opponent => None,
+                _ => Some(1),
+            }
+        };
+        let on_edge = |coord: Coord| match from {
+            Edge::First => self.on_first_edge(color, coord),
+            Edge::Second => self.on_second_edge(color, coord),
+        };
+        let mut distances = vec![u16::MAX; usize::from(size) * usize::from(size)];
+        let index = |coord: Coord| usize::from(coord.y) * usize::from(size) + usize::from(coord.x);
+        // free steps go on the front of the queue and costly ones on the back, so cells are always
+        // taken off the queue in order of distance
+        let mut queue = VecDeque::new();
+        for (coord, _) in self.cells().filter(|&(coord, _)| on_edge(coord)) {
+            if let Some(c) = cost(coord) {
+                distances[index(coord)] = c;
+                if c == 0 {
+                    queue.push_front((c, coord));
+                } else {
+                    queue.push_back((c, coord));
+                }
+            }
+        }
+        while let Some((distance, coord)) = queue.pop_front() {
+            if distance > distances[index(coord)] {
+                // a shorter path here has already been handled
+                continue;
+            }
+            for neighbor in coord.neighbors_iter(size) {
+                if let Some(c) = cost(neighbor) {
+                    if distance + c < distances[index(neighbor)] {
+                        distances[index(neighbor)] = distance + c;
+                        if c == 0 {
+                            queue.push_front((distance, neighbor));
+                        } else {
+                            queue.push_back((distance + c, neighbor));
+                        }
+                    }
+                }
+            }
+        }
+        distances
+    }
 }
     
+/// Solves the linear system `matrix * x = rhs` for `x` by Gaussian elimination with partial
+/// pivoting. The matrix should be square and nonsingular.
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Vec<f64> {
+    let n = rhs.len();
+    for col in 0..n {
+        // swap the row with the largest entry in this column into place, for numerical stability
+        let pivot = (col..n).max_by(|&i, &j| matrix[i][col].abs().total_cmp(&matrix[j][col].abs())).unwrap();
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+        let (top, bottom) = matrix.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for (i, row) in bottom.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            if factor != 0.0 {
+                for (entry, pivot_entry) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *entry -= factor * pivot_entry;
+                }
+                rhs[col + 1 + i] -= factor * rhs[col];
+            }
+        }
+    }
+    // back substitution
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| matrix[row][k] * x[k]).sum();
+        x[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+    x
+}
+
+/// Scores the board for the given color by how many more empty cells the opponent needs to connect
+/// their edges than the color does, as in `connection_distance`. A color that has been cut off
+/// scores negative infinity, and a color whose opponent has been cut off scores positive infinity.
+fn distance_score(board: &Board, color: Color) -> f64 {
+    match (board.connection_distance(color), board.connection_distance(color.opponent())) {
+        (None, _) => f64::NEG_INFINITY,
+        (_, None) => f64::INFINITY,
+        (Some(own), Some(opponent)) => f64::from(opponent) - f64::from(own),
+    }
+}
+
+/// Scores each of the boards for the given color by how many more empty cells the opponent needs
+/// to connect their edges than the color does, as in `connection_distance`, so that higher scores
+/// are better for the color. A color that has been cut off scores negative infinity, and a color
+/// whose opponent has been cut off scores positive infinity. With the `rayon` feature, the boards
+/// are scored in parallel.
+pub fn evaluate_batch(boards: &[Board], color: Color) -> Vec<f64> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        boards.par_iter().map(|board| distance_score(board, color)).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        boards.iter().map(|board| distance_score(board, color)).collect()
+    }
+}
+
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut s = String::new();
-        for y in 0..self.size {
-            for x in 0..self.size {
-                let c = Coord{x: x as u8, y: y as u8};
-                if self.black.contains(&c) {
-                    // add a black hexagon
-                    s.push('⬢');          
-                } else if self.white.contains(&c) {
-                    // add a white hexagon
-                    s.push('⬡');
-                } else {
-                    // add a placeholder dot
-                    s.push('⋅');
-                }
-                // push a space, so that the next row can fit in between these pieces
-                s.push(' ');
+        // black hexagons, white hexagons, and placeholder dots
+        write!(f, "{}", self.diagram('⬢', '⬡', '⋅'))
+    }
+}
+
+/// Parses the diagram drawn by `to_ascii`, with `X` for Black, `O` for White, and `.` for empty
+/// cells. The size is the number of rows, and each row must be indented one more space than the
+/// row above it. Spaces at the ends of the rows and blank lines at the end are ignored. The status
+/// is that of the resulting position.
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.trim_end().lines().collect();
+        let size = rows.len();
+        if size == 0 || size > usize::from(MAX_SIZE) {
+            return Err(ParseBoardError::InvalidSize(size));
+        }
+        let (mut black, mut white) = (vec![], vec![]);
+        for (y, row) in rows.iter().enumerate() {
+            let cells = row.trim_end();
+            if cells.len() - cells.trim_start().len() != y {
+                return Err(ParseBoardError::BadIndentation(y));
             }
-            // separate with a newline and the right number of spaces
-            s.push('\n');
-            for _ in 0..=y {
-                s.push(' ');
+            let cells: Vec<char> = cells.trim_start().chars().collect();
+            // cells are separated by single spaces
+            if cells.len() != 2 * size - 1 || cells.iter().skip(1).step_by(2).any(|&c| c != ' ') {
+                return Err(ParseBoardError::WrongRowLength(y));
+            }
+            for (x, &c) in cells.iter().step_by(2).enumerate() {
+                let coord = Coord{x: x as u8, y: y as u8};
+                match c {
+                    'X' => black.push(coord),
+                    'O' => white.push(coord),
+                    '.' => {}
+                    _ => return Err(ParseBoardError::InvalidCell(c)),
+                }
             }
         }
-        write!(f, "{}", s)
+        // every piece is in its own cell on the board, so this can't fail
+        Ok(Board::with_setup(size as u16, &black, &white).unwrap())
     }
 }
 
@@ -272,6 +1504,301 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    #[test]
+    fn test_parse_loose() {
+        let messy = "\n    X . O\n\t. O .\n\n . .   X   \n";
+        let board = Board::parse_loose(messy, 3).unwrap();
+        assert_eq!(board, "X . O \n . O . \n  . . X \n   ".parse().unwrap());
+        assert_eq!(Board::parse_loose(&board.to_string(), 3), Ok(board.clone()));
+        assert_eq!(Board::parse_loose("X⬡.\n...\n..⋅", 3).unwrap().piece(Coord{x: 1, y: 0}), HexCell::White);
+        assert_eq!(Board::parse_loose(messy, 4), Err(ParseBoardError::WrongRowCount(3)));
+        assert_eq!(Board::parse_loose("X.\n...\n...", 3), Err(ParseBoardError::WrongRowLength(0)));
+        assert_eq!(Board::parse_loose("X.?\n...\n...", 3), Err(ParseBoardError::InvalidCell('?')));
+        assert_eq!(Board::parse_loose("", 0), Err(ParseBoardError::InvalidSize(0)));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let board: Board = "X . O \n . O . \n  . . X \n   ".parse().unwrap();
+        assert_eq!(board.size, 3);
+        assert_eq!(board.piece(Coord{x: 0, y: 0}), HexCell::Black);
+        assert_eq!(board.piece(Coord{x: 2, y: 0}), HexCell::White);
+        assert_eq!(board.piece(Coord{x: 1, y: 1}), HexCell::White);
+        assert_eq!(board.piece(Coord{x: 2, y: 2}), HexCell::Black);
+        assert_eq!(board.piece_count(Color::Black) + board.piece_count(Color::White), 4);
+        assert_eq!(board.to_ascii().parse::<Board>().unwrap(), board);
+
+        let mut won = Board::new(4);
+        for x in 0..4 {
+            won.place_piece(Coord{x, y: 1}, Color::Black);
+        }
+        let parsed: Board = won.to_ascii().parse().unwrap();
+        assert_eq!(parsed, won);
+        assert_eq!(parsed.status(), GameStatus::BlackWin);
+
+        assert_eq!("".parse::<Board>(), Err(ParseBoardError::InvalidSize(0)));
+        assert_eq!("X .\n. .".parse::<Board>(), Err(ParseBoardError::BadIndentation(1)));
+        assert_eq!("X .\n . . .".parse::<Board>(), Err(ParseBoardError::WrongRowLength(1)));
+        assert_eq!("X .\n .  .".parse::<Board>(), Err(ParseBoardError::WrongRowLength(1)));
+        assert_eq!("X #\n . .".parse::<Board>(), Err(ParseBoardError::InvalidCell('#')));
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut before = Board::new(5);
+        before.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        let mut after = before.clone();
+        assert!(before.diff(&after).is_empty());
+        after.place_piece(Coord{x: 1, y: 3}, Color::White);
+        assert_eq!(before.diff(&after), vec![(Coord{x: 1, y: 3}, HexCell::Empty, HexCell::White)]);
+        assert_eq!(after.diff(&before), vec![(Coord{x: 1, y: 3}, HexCell::White, HexCell::Empty)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_diff_size_mismatch() {
+        Board::new(5).diff(&Board::new(6));
+    }
+
+    #[test]
+    fn test_evaluate_batch() {
+        let mut boards = vec![Board::new(5)];
+        for &(x, y) in &[(2, 2), (1, 3), (3, 1), (0, 4), (4, 0), (2, 3)] {
+            let mut board = boards.last().unwrap().clone();
+            let color = if boards.len() % 2 == 1 { Color::Black } else { Color::White };
+            board.place_piece(Coord{x, y}, color);
+            boards.push(board);
+        }
+        let mut cut_off = Board::new(2);
+        cut_off.place_piece(Coord{x: 0, y: 0}, Color::White);
+        cut_off.place_piece(Coord{x: 0, y: 1}, Color::White);
+        boards.push(cut_off);
+        let serial: Vec<f64> = boards.iter().map(|board| distance_score(board, Color::Black)).collect();
+        assert_eq!(evaluate_batch(&boards, Color::Black), serial);
+        assert_eq!(serial[0], 0.0);
+        assert_eq!(serial[1], 1.0);
+        assert_eq!(serial.last(), Some(&f64::NEG_INFINITY));
+        assert_eq!(evaluate_batch(&boards, Color::White).last(), Some(&f64::INFINITY));
+    }
+
+    #[test]
+    fn test_orientation() {
+        for &orientation in &[Orientation::Standard, Orientation::Reversed] {
+            // the color connecting the top and bottom edges
+            let vertical = match orientation {
+                Orientation::Standard => Color::White,
+                Orientation::Reversed => Color::Black,
+            };
+            for &color in &[vertical, vertical.opponent()] {
+                let mut board = Board::new_with_orientation(5, orientation);
+                assert_eq!(board.orientation(), orientation);
+                for y in 0..5 {
+                    board.place_piece(Coord{x: 2, y}, color);
+                }
+                let expected = match (color == vertical, color) {
+                    (true, Color::Black) => GameStatus::BlackWin,
+                    (true, Color::White) => GameStatus::WhiteWin,
+                    (false, _) => GameStatus::Ongoing,
+                };
+                assert_eq!(board.status(), expected);
+                assert_eq!(board.connection_distance(color), Some(if color == vertical { 0 } else { 4 }));
+                let mut rebuilt = board.clone();
+                rebuilt.remove_piece(Coord{x: 2, y: 4});
+                rebuilt.place_piece(Coord{x: 2, y: 4}, color);
+                assert_eq!(rebuilt.status(), expected);
+            }
+        }
+        // a reversed board behaves like a standard one with the colors swapped
+        let mut reversed = Board::new_with_orientation(5, Orientation::Reversed);
+        let mut swapped = Board::new(5);
+        for (i, &(x, y)) in [(2, 2), (1, 3), (3, 1), (0, 4), (4, 0), (2, 3), (1, 1)].iter().enumerate() {
+            let color = if i % 2 == 0 { Color::Black } else { Color::White };
+            reversed.place_piece(Coord{x, y}, color);
+            swapped.place_piece(Coord{x, y}, color.opponent());
+        }
+        for &color in &[Color::Black, Color::White] {
+            assert_eq!(reversed.resistance_score(color), swapped.resistance_score(color.opponent()));
+            assert_eq!(reversed.connection_distance(color), swapped.connection_distance(color.opponent()));
+        }
+        assert_eq!(reversed.dead_cells(), swapped.dead_cells());
+        assert!(reversed.edge(Color::Black, Edge::First).all(|coord| coord.y == 0));
+        assert_ne!(Board::new_with_orientation(3, Orientation::Reversed), Board::new(3));
+    }
+
+    #[test]
+    fn test_canonical() {
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 3, y: 4}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 1}, Color::White);
+        let rotated = board.rotate_180();
+        assert_ne!(board, rotated);
+        assert_eq!(board.canonical(), rotated.canonical());
+        // the rotation puts Black's piece in the top row, so it comes first
+        assert_eq!(board.canonical(), rotated);
+        assert_eq!(rotated.canonical(), rotated);
+    }
+
+    #[test]
+    fn test_to_planes() {
+        let mut board = Board::new(4);
+        board.place_piece(Coord{x: 1, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 1}, Color::White);
+        let planes = board.to_planes(Color::White);
+        assert_eq!(planes.len(), 48);
+        let sum = |plane: usize| planes[plane * 16..(plane + 1) * 16].iter().sum::<f32>();
+        assert_eq!(sum(0), board.piece_count(Color::Black) as f32);
+        assert_eq!(sum(1), board.piece_count(Color::White) as f32);
+        assert_eq!(sum(2), 0.0);
+        assert_eq!(planes[1], 1.0);
+        assert_eq!(planes[16 + 7], 1.0);
+        assert_eq!(board.to_planes(Color::Black)[32..], [1.0; 16]);
+    }
+
+    #[test]
+    fn test_is_full() {
+        let mut board = Board::new(2);
+        assert!(!board.is_full());
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        assert!(!board.is_full());
+        board.place_piece(Coord{x: 1, y: 1}, Color::White);
+        assert!(board.is_full());
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        for size in 1..=5 {
+            let board = Board::new(size);
+            let last = (size - 1) as u8;
+            // the obtuse corners have three neighbors and the acute corners two, except on boards so
+            // small that the corners meet
+            let counts: Vec<usize> = [(0, 0), (last, 0), (0, last), (last, last)].iter()
+                .map(|&(x, y)| board.neighbors(Coord{x, y}).len())
+                .collect();
+            match size {
+                1 => assert_eq!(counts, vec![0, 0, 0, 0]),
+                _ => assert_eq!(counts, vec![2, 3, 3, 2]),
+            }
+            for (coord, _) in board.cells() {
+                assert!(board.neighbors(coord).iter().all(|n| u16::from(n.x) < size && u16::from(n.y) < size));
+            }
+        }
+        assert_eq!(Board::new(3).neighbors(Coord{x: 2, y: 0}),
+                   vec![Coord{x: 2, y: 1}, Coord{x: 1, y: 1}, Coord{x: 1, y: 0}]);
+    }
+
+    #[test]
+    fn test_empty_neighbors() {
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 2, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 2}, Color::White);
+        board.place_piece(Coord{x: 1, y: 2}, Color::White);
+        assert_eq!(board.empty_neighbors(Coord{x: 2, y: 2}),
+                   vec![Coord{x: 3, y: 1}, Coord{x: 2, y: 3}, Coord{x: 1, y: 3}]);
+        // neighbors off the board aren't included
+        assert_eq!(board.empty_neighbors(Coord{x: 4, y: 0}), vec![Coord{x: 4, y: 1}, Coord{x: 3, y: 1}, Coord{x: 3, y: 0}]);
+    }
+
+    #[test]
+    fn test_edge_distance_map() {
+        // on an empty board, a straight line through any cell crosses the board
+        let board = Board::new(5);
+        assert_eq!(board.edge_distance_map(Color::Black), vec![5; 25]);
+        assert_eq!(board.edge_distance_map(Color::White), vec![5; 25]);
+
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 1}, Color::White);
+        let map = board.edge_distance_map(Color::Black);
+        assert_eq!(map[12], 4);
+        assert_eq!(map[7], u16::MAX);
+        // the cells beside the center piece share its connection, and the map is symmetric
+        assert_eq!(map[11], 4);
+        assert_eq!(map[13], 4);
+        assert_eq!(map[0], 5);
+        assert_eq!(map[24], 5);
+        assert_eq!(map.iter().min().cloned(), board.connection_distance(Color::Black));
+    }
+
+    #[test]
+    fn test_place_piece_verbose() {
+        let mut board = Board::new(5);
+        let report = board.place_piece_verbose(Coord{x: 2, y: 1}, Color::Black).unwrap();
+        assert!(report.merged.is_empty());
+        board.place_piece(Coord{x: 3, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 3}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 3}, Color::White);
+        // (2, 2) joins the pair above it and the single stone below it to the left
+        let report = board.place_piece_verbose(Coord{x: 2, y: 2}, Color::Black).unwrap();
+        assert_eq!(report.merged, vec![Coord{x: 2, y: 1}, Coord{x: 1, y: 3}]);
+        assert_eq!(report.won, None);
+        assert_eq!(board.piece(Coord{x: 2, y: 2}), HexCell::Black);
+        assert_eq!(board.place_piece_verbose(Coord{x: 2, y: 2}, Color::White), None);
+        assert_eq!(board.place_piece_verbose(Coord{x: 5, y: 0}, Color::White), None);
+    }
+
+    #[test]
+    fn test_apply_swap() {
+        let mut board = Board::new(5);
+        assert!(!board.apply_swap());
+        board.place_piece(Coord{x: 3, y: 1}, Color::Black);
+        assert!(board.apply_swap());
+        assert_eq!(board.piece(Coord{x: 1, y: 3}), HexCell::White);
+        assert_eq!(board.piece(Coord{x: 3, y: 1}), HexCell::Empty);
+        assert_eq!(board.piece_count(Color::Black), 0);
+        // swapping again undoes it
+        assert!(board.apply_swap());
+        assert_eq!(board.piece(Coord{x: 3, y: 1}), HexCell::Black);
+        board.place_piece(Coord{x: 0, y: 0}, Color::White);
+        assert!(!board.apply_swap());
+        assert_eq!(board.piece(Coord{x: 3, y: 1}), HexCell::Black);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Board::try_new(0), Err(BoardSizeError(0)));
+        assert_eq!(Board::try_new(1).unwrap().cells().count(), 1);
+        assert_eq!(Board::try_new(26).unwrap().size, 26);
+        assert_eq!(Board::try_new(27), Err(BoardSizeError(27)));
+        assert_eq!(Board::try_new(u16::MAX), Err(BoardSizeError(u16::MAX)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_large() {
+        Board::new(27);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_setup_empty_size() {
+        let _ = Board::with_setup(0, &[], &[]);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let board = Board::new(5);
+        assert_eq!(board.long_diagonal(), Coord::parse_list("a1 b2 c3 d4 e5").unwrap());
+        assert_eq!(board.short_diagonal(), Coord::parse_list("e1 d2 c3 b4 a5").unwrap());
+        assert!(board.long_diagonal().iter().all(|&c| c.reflect_long_diagonal(5) == c));
+        assert!(board.short_diagonal().iter().all(|&c| c.reflect_short_diagonal(5) == c));
+    }
+
+    #[test]
+    fn test_edge() {
+        let board = Board::new(4);
+        let left: Vec<Coord> = board.edge(Color::Black, Edge::First).collect();
+        let right: Vec<Coord> = board.edge(Color::Black, Edge::Second).collect();
+        assert_eq!(left, (0..4).map(|y| Coord{x: 0, y}).collect::<Vec<_>>());
+        assert_eq!(right, (0..4).map(|y| Coord{x: 3, y}).collect::<Vec<_>>());
+        let bottom: Vec<Coord> = board.edge(Color::White, Edge::Second).collect();
+        assert_eq!(bottom, (0..4).map(|x| Coord{x, y: 3}).collect::<Vec<_>>());
+        assert!(board.edge(Color::White, Edge::First).all(|coord| coord.y == 0));
+    }
+
     #[test]
     fn test_display() {
         let mut board = Board::new(5);
@@ -291,6 +1818,51 @@ mod tests {
         println!("{}", board);
     }
 
+    #[test]
+    fn test_to_ascii() {
+        let mut board = Board::new(3);
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 1}, Color::White);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 0}, Color::White);
+        assert_eq!(board.to_ascii(), "X . O \n . O . \n  . . X \n   ");
+        assert_eq!(board.to_string(), "⬢ ⋅ ⬡ \n ⋅ ⬡ ⋅ \n  ⋅ ⋅ ⬢ \n   ");
+    }
+
+    #[test]
+    fn test_to_labeled_string_with() {
+        let mut board = Board::new(3);
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 1}, Color::White);
+        assert_eq!(board.to_labeled_string_with(DisplayConfig::default()), board.to_labeled_string());
+        let transposed = DisplayConfig { letters_on_rows: true, origin_bottom_left: true };
+        assert_eq!(board.to_labeled_string_with(transposed), "  1 2 3\nc ⬢ ⋅ ⋅\nb  ⋅ ⋅ ⬡\na   ⋅ ⋅ ⋅\n");
+        let bottom = DisplayConfig { origin_bottom_left: true, ..DisplayConfig::default() };
+        assert!(board.to_labeled_string_with(bottom).starts_with("  a b c\n3 ⬢"));
+
+        // two-digit column numbers are written downwards
+        let labeled = Board::new(11).to_labeled_string_with(transposed);
+        let lines: Vec<&str> = labeled.lines().collect();
+        assert_eq!(lines[0], "                    1 1");
+        assert_eq!(lines[1], "  1 2 3 4 5 6 7 8 9 0 1");
+        assert!(lines[2].starts_with("k ⋅"));
+    }
+
+    #[test]
+    fn test_to_labeled_string() {
+        let mut board = Board::new(3);
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 1}, Color::White);
+        assert_eq!(board.to_labeled_string(), "  a b c\n1 ⬢ ⋅ ⋅\n2  ⋅ ⋅ ⬡\n3   ⋅ ⋅ ⋅\n");
+
+        // two-digit row numbers are right-aligned
+        let labeled = Board::new(10).to_labeled_string();
+        let lines: Vec<&str> = labeled.lines().collect();
+        assert_eq!(lines[0], "   a b c d e f g h i j");
+        assert!(lines[1].starts_with(" 1 ⋅"));
+        assert!(lines[10].starts_with("10          ⋅"));
+    }
+
     #[test]
     fn test_game_status() {
         let mut board = Board::new(5);
@@ -328,6 +1900,520 @@ mod tests {
         println!("{}", board2);
     }
 
+    #[test]
+    fn test_virtual_edges() {
+        let board = Board::new(7);
+        for num in 0..(9 * 9) {
+            let (row, col) = (num / 9, num % 9);
+            let expected = if col == 0 || col == 8 {
+                HexCell::Black
+            } else if row == 0 || row == 8 {
+                HexCell::White
+            } else {
+                HexCell::Empty
+            };
+            assert_eq!(board.piece_at_num(num), expected);
+        }
+    }
+
+    #[test]
+    fn test_white_vertical_chain() {
+        let mut board = Board::new(7);
+        for y in 0..6 {
+            board.place_piece(Coord{x: 5, y}, Color::White);
+        }
+        // the chain stops one short of the bottom edge
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 6, y: 6}, Color::White);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 5, y: 6}, Color::White);
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+
+        // a chain whose only route to the top edge runs through Black's right edge doesn't win
+        let mut board = Board::new(7);
+        for y in 1..7 {
+            board.place_piece(Coord{x: 6, y}, Color::White);
+        }
+        board.place_piece(Coord{x: 6, y: 0}, Color::Black);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+    }
+
+    #[test]
+    fn test_place_result() {
+        let mut board = Board::new(3);
+        assert_eq!(board.place_piece(Coord{x: 0, y: 1}, Color::Black), PlaceResult { placed: true, won: None });
+        assert_eq!(board.place_piece(Coord{x: 0, y: 1}, Color::White), PlaceResult { placed: false, won: None });
+        assert_eq!(board.place_piece(Coord{x: 3, y: 1}, Color::White), PlaceResult { placed: false, won: None });
+        board.place_piece(Coord{x: 1, y: 1}, Color::Black);
+        assert_eq!(board.place_piece(Coord{x: 2, y: 1}, Color::Black), PlaceResult { placed: true, won: Some(Color::Black) });
+        assert_eq!(board.status(), GameStatus::BlackWin);
+        // moves after the winning one don't win again
+        assert_eq!(board.place_piece(Coord{x: 2, y: 0}, Color::Black), PlaceResult { placed: true, won: None });
+
+        let mut board = Board::new(2);
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        assert_eq!(board.place_piece(Coord{x: 0, y: 1}, Color::White).won, Some(Color::White));
+    }
+
+    #[test]
+    fn test_are_connected() {
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 0}, Color::Black);
+        let (a, b, isolated) = (Coord{x: 2, y: 2}, Coord{x: 3, y: 2}, Coord{x: 2, y: 0});
+        assert!(board.are_connected(a, b, Color::Black));
+        assert!(board.are_connected(b, a, Color::Black));
+        assert!(!board.are_connected(a, isolated, Color::Black));
+        // the pieces have to be of the given color
+        assert!(!board.are_connected(a, b, Color::White));
+        assert!(!board.are_connected(a, Coord{x: 4, y: 4}, Color::Black));
+
+        // pieces touching the same edge are connected through it
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 0, y: 4}, Color::Black);
+        assert!(board.are_connected(Coord{x: 0, y: 0}, Coord{x: 0, y: 4}, Color::Black));
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut board = Board::new(5);
+        assert!(board.groups(Color::Black).is_empty());
+        for &(x, y) in &[(3, 3), (0, 0), (2, 3), (1, 0), (0, 4)] {
+            board.place_piece(Coord{x, y}, Color::Black);
+        }
+        board.place_piece(Coord{x: 1, y: 1}, Color::White);
+        // a1 and a5 are both on the left edge, but aren't in the same group
+        assert_eq!(board.groups(Color::Black), vec![
+            vec![Coord{x: 0, y: 0}, Coord{x: 1, y: 0}],
+            vec![Coord{x: 2, y: 3}, Coord{x: 3, y: 3}],
+            vec![Coord{x: 0, y: 4}],
+        ]);
+        assert_eq!(board.groups(Color::White), vec![vec![Coord{x: 1, y: 1}]]);
+    }
+
+    #[test]
+    fn test_with_setup() {
+        let black = [Coord{x: 0, y: 2}, Coord{x: 1, y: 2}, Coord{x: 3, y: 2}];
+        let white = [Coord{x: 2, y: 0}, Coord{x: 2, y: 1}];
+        let mut board = Board::with_setup(4, &black, &white).unwrap();
+        assert_eq!(board.piece(Coord{x: 1, y: 2}), HexCell::Black);
+        assert_eq!(board.piece(Coord{x: 2, y: 1}), HexCell::White);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        assert_eq!(board.place_piece(Coord{x: 2, y: 2}, Color::Black).won, Some(Color::Black));
+
+        // a position that has already been won
+        let board = Board::with_setup(2, &[], &[Coord{x: 1, y: 0}, Coord{x: 0, y: 1}]).unwrap();
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+
+        assert_eq!(Board::with_setup(4, &[Coord{x: 4, y: 0}], &[]).unwrap_err(), MoveError::OutOfBounds);
+        assert_eq!(Board::with_setup(4, &black, &[Coord{x: 1, y: 2}]).unwrap_err(),
+                   MoveError::Occupied(HexCell::Black));
+        assert_eq!(Board::with_setup(4, &white, &white).unwrap_err(), MoveError::Occupied(HexCell::Black));
+    }
+
+    #[test]
+    fn test_remove_piece() {
+        let mut board = Board::new(5);
+        assert!(!board.remove_piece(Coord{x: 2, y: 2}));
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 4, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 0, y: 2}, Color::White);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+
+        // taking back the winning move returns the game to ongoing
+        assert!(board.remove_piece(Coord{x: 4, y: 0}));
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        assert_eq!(board.piece(Coord{x: 4, y: 0}), HexCell::Empty);
+        board.place_piece(Coord{x: 4, y: 0}, Color::Black);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+
+        // removing from the middle of the chain splits it in two
+        assert!(board.remove_piece(Coord{x: 1, y: 2}));
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        assert!(board.remove_piece(Coord{x: 0, y: 2}));
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut board = Board::new(3);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+        board.reset();
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        assert!(board.cells().all(|(_, cell)| cell == HexCell::Empty));
+
+        // the board plays the same as a new one afterwards
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        board.place_piece(Coord{x: 1, y: 1}, Color::White);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        board.place_piece(Coord{x: 0, y: 2}, Color::White);
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+    }
+
+    #[test]
+    fn test_transform() {
+        let mut board = Board::new(5);
+        for &(x, y) in &[(0, 1), (1, 1), (2, 0), (3, 0), (4, 0)] {
+            board.place_piece(Coord{x, y}, Color::Black);
+        }
+        board.place_piece(Coord{x: 2, y: 2}, Color::White);
+        board.place_piece(Coord{x: 4, y: 3}, Color::White);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+
+        let rotated = board.rotate_180();
+        assert_eq!(rotated.status(), GameStatus::BlackWin);
+        assert_eq!(rotated.piece(Coord{x: 4, y: 3}), HexCell::Black);
+        assert_eq!(rotated.piece(Coord{x: 0, y: 1}), HexCell::White);
+
+        let reflected = board.reflect_long_diagonal();
+        assert_eq!(reflected.status(), GameStatus::WhiteWin);
+        assert_eq!(reflected.piece(Coord{x: 1, y: 0}), HexCell::White);
+        assert_eq!(reflected.piece(Coord{x: 3, y: 4}), HexCell::Black);
+
+        assert_eq!(board.transform(Symmetry::ReflectShortDiagonal).status(), GameStatus::WhiteWin);
+        assert_eq!(board.transform(Symmetry::Identity).status(), GameStatus::BlackWin);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let board = Board::new(4);
+        let (width, height) = board.bounding_box(10.0);
+        let radius = 10.0 / 3f64.sqrt();
+        let (right, _) = Coord{x: 3, y: 3}.to_pixel(10.0);
+        assert!((width - (right + 5.0)).abs() < 1e-9);
+        let (_, bottom) = Coord{x: 0, y: 3}.to_pixel(10.0);
+        assert!((height - (bottom + radius)).abs() < 1e-9);
+        assert_eq!(Board::new(1).bounding_box(10.0).0, 10.0);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let mut board = Board::new(4);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 0}, Color::White);
+        let svg = board.to_svg(20.0);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<polygon").count(), 16);
+        assert_eq!(svg.matches("fill=\"#000000\"").count(), 1);
+        assert_eq!(svg.matches("<polyline").count(), 4);
+        assert!(!svg.contains("<text"));
+
+        let labeled = board.to_labeled_svg(20.0);
+        assert_eq!(labeled.matches("<polygon").count(), 16);
+        assert_eq!(labeled.matches("<text").count(), 14);
+        assert!(labeled.contains(">a1</text>"));
+        assert!(!labeled.contains(">b3</text>"));
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut board = Board::new(4);
+        assert_eq!(board.piece_count(Color::Black), 0);
+        assert_eq!(board.empty_count(), 16);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::White);
+        board.place_piece(Coord{x: 3, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 0}, Color::White);
+        assert_eq!(board.piece_count(Color::Black), 2);
+        assert_eq!(board.piece_count(Color::White), 1);
+        assert_eq!(board.empty_count(), 13);
+    }
+
+    #[test]
+    fn test_cells() {
+        let mut board = Board::new(3);
+        board.place_piece(Coord{x: 1, y: 0}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::White);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        let cells: Vec<(Coord, HexCell)> = board.cells().collect();
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[0], (Coord{x: 0, y: 0}, HexCell::Empty));
+        assert_eq!(cells[1], (Coord{x: 1, y: 0}, HexCell::Black));
+        assert_eq!(cells[3], (Coord{x: 0, y: 1}, HexCell::Black));
+        assert_eq!(cells[8], (Coord{x: 2, y: 2}, HexCell::White));
+        assert_eq!(cells.iter().filter(|(_, cell)| *cell == HexCell::Empty).count(), 6);
+
+        let mut black: Vec<Coord> = board.pieces(Color::Black).collect();
+        black.sort();
+        assert_eq!(black, vec![Coord{x: 1, y: 0}, Coord{x: 0, y: 1}]);
+        assert_eq!(board.pieces(Color::White).collect::<Vec<Coord>>(), vec![Coord{x: 2, y: 2}]);
+    }
+
+    #[test]
+    fn test_winning_path() {
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 0, y: 0}, Color::White);
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        board.place_piece(Coord{x: 1, y: 1}, Color::White);
+        board.place_piece(Coord{x: 2, y: 1}, Color::White);
+        board.place_piece(Coord{x: 2, y: 2}, Color::White);
+        board.place_piece(Coord{x: 1, y: 3}, Color::White);
+        board.place_piece(Coord{x: 4, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 4}, Color::Black);
+        assert_eq!(board.winning_path(), None);
+        board.place_piece(Coord{x: 0, y: 4}, Color::White);
+        assert_eq!(board.status(), GameStatus::WhiteWin);
+
+        let path = board.winning_path().unwrap();
+        assert_eq!(path.first().unwrap().y, 0);
+        assert_eq!(path.last().unwrap().y, 4);
+        assert!(path.iter().all(|&c| board.piece(c) == HexCell::White));
+        assert!(path.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+        // (0, 0) is a dead end, so the shortest path starts from (1, 0)
+        assert_eq!(path, vec![Coord{x: 1, y: 0}, Coord{x: 1, y: 1}, Coord{x: 2, y: 1},
+                              Coord{x: 2, y: 2}, Coord{x: 1, y: 3}, Coord{x: 0, y: 4}]);
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |board: &Board| {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+        let coords = [Coord{x: 0, y: 0}, Coord{x: 3, y: 1}, Coord{x: 2, y: 2}, Coord{x: 1, y: 3}];
+        let mut forward = Board::new(4);
+        let mut backward = Board::new(4);
+        for (i, &coord) in coords.iter().enumerate() {
+            forward.place_piece(coord, if i % 2 == 0 { Color::Black } else { Color::White });
+        }
+        for (i, &coord) in coords.iter().enumerate().rev() {
+            backward.place_piece(coord, if i % 2 == 0 { Color::Black } else { Color::White });
+        }
+        assert_eq!(forward, backward);
+        assert_eq!(hash(&forward), hash(&backward));
+
+        // the same stones in different colors or on a different size of board aren't equal
+        let mut different = backward.clone();
+        different.remove_piece(coords[0]);
+        different.place_piece(coords[0], Color::White);
+        assert_ne!(forward, different);
+        assert_ne!(Board::new(4), Board::new(5));
+    }
+
+    #[test]
+    fn test_winning_move() {
+        let mut board = Board::new(4);
+        for &(x, y) in &[(0, 1), (1, 1), (3, 1)] {
+            board.place_piece(Coord{x, y}, Color::Black);
+        }
+        assert_eq!(board.winning_move(), None);
+        board.place_piece(Coord{x: 2, y: 1}, Color::Black);
+        assert_eq!(board.status(), GameStatus::BlackWin);
+        assert_eq!(board.winning_move(), Some(Coord{x: 2, y: 1}));
+        assert_eq!(board.winning_move(), board.last_move());
+        assert!(board.winning_path().unwrap().contains(&Coord{x: 2, y: 1}));
+        // later pieces don't change it, but removing one of the winner's does
+        board.place_piece(Coord{x: 0, y: 0}, Color::White);
+        board.place_piece(Coord{x: 3, y: 3}, Color::Black);
+        assert_eq!(board.winning_move(), Some(Coord{x: 2, y: 1}));
+        assert_eq!(board.rotate_180().winning_move(), Some(Coord{x: 1, y: 2}));
+        board.remove_piece(Coord{x: 0, y: 0});
+        assert_eq!(board.winning_move(), Some(Coord{x: 2, y: 1}));
+        board.remove_piece(Coord{x: 3, y: 3});
+        assert_eq!(board.winning_move(), None);
+        let setup = Board::with_setup(2, &[Coord{x: 0, y: 0}, Coord{x: 1, y: 0}], &[]).unwrap();
+        assert_eq!(setup.status(), GameStatus::BlackWin);
+        assert_eq!(setup.winning_move(), None);
+    }
+
+    #[test]
+    fn test_last_move() {
+        let mut board = Board::new(5);
+        assert_eq!(board.last_move(), None);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 0}, Color::White);
+        assert_eq!(board.last_move(), Some(Coord{x: 3, y: 0}));
+        // rejected placements don't change it
+        board.place_piece(Coord{x: 1, y: 2}, Color::White);
+        board.place_piece(Coord{x: 7, y: 0}, Color::White);
+        assert_eq!(board.last_move(), Some(Coord{x: 3, y: 0}));
+        assert_eq!(board.rotate_180().last_move(), Some(Coord{x: 1, y: 4}));
+        board.remove_piece(Coord{x: 1, y: 2});
+        assert_eq!(board.last_move(), Some(Coord{x: 3, y: 0}));
+        board.remove_piece(Coord{x: 3, y: 0});
+        assert_eq!(board.last_move(), None);
+        board.place_piece(Coord{x: 0, y: 0}, Color::Black);
+        board.reset();
+        assert_eq!(board.last_move(), None);
+    }
+
+    #[test]
+    fn test_zobrist_hash() {
+        let mut board = Board::new(5);
+        assert_eq!(board.zobrist_hash(), 0);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        let before = board.zobrist_hash();
+        assert_ne!(before, 0);
+        board.place_piece(Coord{x: 1, y: 3}, Color::White);
+        assert_ne!(board.zobrist_hash(), before);
+        board.remove_piece(Coord{x: 1, y: 3});
+        assert_eq!(board.zobrist_hash(), before);
+
+        // the same position reached another way has the same hash
+        let mut other = Board::new(5);
+        other.place_piece(Coord{x: 0, y: 4}, Color::White);
+        other.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        other.remove_piece(Coord{x: 0, y: 4});
+        assert_eq!(other.zobrist_hash(), before);
+        // but the same cell with the other color doesn't
+        let mut white = Board::new(5);
+        white.place_piece(Coord{x: 2, y: 2}, Color::White);
+        assert_ne!(white.zobrist_hash(), before);
+
+        board.reset();
+        assert_eq!(board.zobrist_hash(), 0);
+    }
+
+    #[test]
+    fn test_dead_cells() {
+        let center = Coord{x: 2, y: 2};
+        let ring = center.neighbors();
+        let surround = |colors: [Color; 6]| {
+            let mut board = Board::new(5);
+            for (&coord, &color) in ring.iter().zip(colors.iter()) {
+                board.place_piece(coord, color);
+            }
+            board
+        };
+        let (b, w) = (Color::Black, Color::White);
+        assert!(Board::new(5).dead_cells().is_empty());
+
+        // a filled ring with each color on one side
+        let dead = surround([b, b, b, w, w, w]).dead_cells();
+        assert!(dead.contains(&center));
+        // a4 is between the left edge and two White pieces, so it's dead too
+        assert!(dead.contains(&Coord{x: 0, y: 3}));
+        assert!(!dead.contains(&Coord{x: 0, y: 2}));
+        // alternating colors make the center cell important to both players
+        assert!(!surround([b, w, b, w, b, w]).dead_cells().contains(&center));
+        // pairs can still be connected through the center
+        assert!(!surround([b, b, w, w, b, w]).dead_cells().contains(&center));
+
+        // four pieces in a row kill the cell even with empty neighbors
+        let mut board = Board::new(5);
+        for &coord in &ring[..4] {
+            board.place_piece(coord, Color::White);
+        }
+        assert!(board.dead_cells().contains(&center));
+        let mut board = Board::new(5);
+        for &coord in &ring[..3] {
+            board.place_piece(coord, Color::White);
+        }
+        assert!(!board.dead_cells().contains(&center));
+
+        // the edges count as pieces: a1 with Black on b1 has Black's left edge and White's top edge
+        // on one side each, with b1 between them
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 0, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 1, y: 0}, Color::White);
+        assert!(board.dead_cells().contains(&Coord{x: 0, y: 0}));
+    }
+
+    #[test]
+    fn test_bridges() {
+        let mut board = Board::new(5);
+        // a bridge between b2 and c3, carried by c2 and b3
+        board.place_piece(Coord{x: 1, y: 1}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        // a bridge between c3 and b5, carried by b4 and c4
+        board.place_piece(Coord{x: 1, y: 4}, Color::Black);
+        // adjacent pieces don't form bridges
+        board.place_piece(Coord{x: 3, y: 2}, Color::Black);
+        assert_eq!(board.bridges(Color::Black), vec![
+            (Coord{x: 1, y: 1}, Coord{x: 2, y: 2}, [Coord{x: 2, y: 1}, Coord{x: 1, y: 2}]),
+            (Coord{x: 2, y: 2}, Coord{x: 1, y: 4}, [Coord{x: 1, y: 3}, Coord{x: 2, y: 3}]),
+        ]);
+        assert!(board.bridges(Color::White).is_empty());
+
+        // an intrusion into a carrier breaks the bridge
+        board.place_piece(Coord{x: 1, y: 2}, Color::White);
+        assert_eq!(board.bridges(Color::Black).len(), 1);
+    }
+
+    #[test]
+    fn test_resistance_score() {
+        // a single empty cell between the edges is two half-cells in series
+        let board = Board::new(1);
+        assert!((board.resistance_score(Color::Black) - 0.5).abs() < 1e-9);
+        assert!((board.resistance_score(Color::White) - 0.5).abs() < 1e-9);
+
+        // the empty board is symmetric, so neither player is favored
+        let mut board = Board::new(5);
+        let empty = board.resistance_score(Color::Black);
+        assert!((empty - board.resistance_score(Color::White)).abs() < 1e-9);
+
+        // adding a stone helps that player and hurts the other
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        let one_stone = board.resistance_score(Color::Black);
+        assert!(one_stone > empty);
+        assert!(board.resistance_score(Color::White) < empty);
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        assert!(board.resistance_score(Color::Black) > one_stone);
+
+        // a finished connection conducts perfectly, and cuts off the opponent
+        for x in [0, 3, 4] {
+            board.place_piece(Coord{x, y: 2}, Color::Black);
+        }
+        assert_eq!(board.resistance_score(Color::Black), f64::INFINITY);
+        assert_eq!(board.resistance_score(Color::White), 0.0);
+    }
+
+    #[test]
+    fn test_connection_distance() {
+        let mut board = Board::new(5);
+        assert_eq!(board.connection_distance(Color::Black), Some(5));
+        assert_eq!(board.connection_distance(Color::White), Some(5));
+        board.place_piece(Coord{x: 1, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        board.place_piece(Coord{x: 3, y: 1}, Color::Black);
+        assert_eq!(board.connection_distance(Color::Black), Some(2));
+        // White must go around Black's stones
+        assert_eq!(board.connection_distance(Color::White), Some(5));
+        for y in 0..5 {
+            board.place_piece(Coord{x: 0, y}, Color::White);
+        }
+        assert_eq!(board.connection_distance(Color::Black), None);
+        assert_eq!(board.connection_distance(Color::White), Some(0));
+
+        // one stone away from winning
+        let mut board = Board::new(4);
+        for x in [0, 1, 3] {
+            board.place_piece(Coord{x, y: 1}, Color::Black);
+        }
+        assert_eq!(board.connection_distance(Color::Black), Some(1));
+    }
+
+    #[test]
+    fn test_win_probability() {
+        let mut board = Board::new(5);
+        assert!((board.win_probability(Color::Black) - 0.5).abs() < 1e-9);
+        board.place_piece(Coord{x: 2, y: 2}, Color::Black);
+        let black = board.win_probability(Color::Black);
+        assert!(black > 0.5 && black < 1.0);
+        assert!((black + board.win_probability(Color::White) - 1.0).abs() < 1e-9);
+        for x in [0, 1, 3, 4] {
+            board.place_piece(Coord{x, y: 2}, Color::Black);
+        }
+        assert_eq!(board.status(), GameStatus::BlackWin);
+        assert_eq!(board.win_probability(Color::Black), 1.0);
+        assert_eq!(board.win_probability(Color::White), 0.0);
+    }
+
     #[test]
     fn test_coord_num_conversion() {
         let board = Board::new(5);
@@ -0,0 +1,103 @@
+//! The colors of the players and the contents of a cell, which every board type shares. Like
+//! `coord`, this doesn't need `std`, so it's available without the `std` feature.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One of the two possible colors in Hex.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Color {
+    /// The left-right player that goes first.
+    Black,
+    /// The top-bottom player that goes second.
+    White,
+}
+
+/// A simple descriptor of the possible values at a Hex tile: black piece, white piece, or empty.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HexCell {
+    /// A Black piece.
+    Black,
+    /// A White piece.
+    White,
+    /// An empty cell.
+    Empty
+}
+
+impl Color {
+    /// Returns the other color.
+    pub fn opponent(self) -> Color {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+}
+
+impl From<Color> for HexCell {
+    fn from(color: Color) -> HexCell {
+        match color {
+            Color::Black => HexCell::Black,
+            Color::White => HexCell::White,
+        }
+    }
+}
+
+/// An error for converting an empty `HexCell` into a `Color`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EmptyCellError;
+
+impl fmt::Display for EmptyCellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an empty cell has no color")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EmptyCellError {}
+
+impl TryFrom<HexCell> for Color {
+    type Error = EmptyCellError;
+
+    fn try_from(cell: HexCell) -> Result<Color, EmptyCellError> {
+        match cell {
+            HexCell::Black => Ok(Color::Black),
+            HexCell::White => Ok(Color::White),
+            HexCell::Empty => Err(EmptyCellError),
+        }
+    }
+}
+
+/// A simple descriptor of the game status: ongoing, black victory, or white victory.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameStatus {
+    /// Black wins.
+    BlackWin,
+    /// White wins.
+    WhiteWin,
+    /// The game does not yet have a winner.
+    Ongoing
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_color_conversions() {
+        assert_eq!(HexCell::from(Color::Black), HexCell::Black);
+        assert_eq!(HexCell::from(Color::White), HexCell::White);
+        assert_eq!(Color::try_from(HexCell::Black), Ok(Color::Black));
+        assert_eq!(Color::try_from(HexCell::White), Ok(Color::White));
+        assert_eq!(Color::try_from(HexCell::Empty), Err(EmptyCellError));
+        assert_eq!(Color::Black.opponent(), Color::White);
+        assert_eq!(Color::White.opponent(), Color::Black);
+    }
+}
@@ -7,16 +7,29 @@
 //! 26x26. Thus, these coordinates do not work for higher board sizes, as it breaks the string
 //! representations and integer arithmetic.
 
-use std::ops::Add;
+use core::cmp::Ordering;
+use core::ops::Add;
+use core::fmt;
+use core::str::FromStr;
+use core::num::ParseIntError;
+#[cfg(feature = "std")]
 use std::error;
-use std::fmt;
-use std::str::FromStr;
-use std::num::ParseIntError;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// The alphabet used for representing coordinates, in lowercase.
-static ALPHABET: &str = "abcdefghjiklmnopqrstuvwxyz";
+pub(crate) static ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// The square root of 3, which relates the width of a hex to its height.
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// A coordinate on a Hex board, such that (x, y) = (0, 0) is the top left and (1, 0) is the hex
 /// immediately to the right of that hex. Cannot support boards larger than 128x128 for performance
 /// reasons.
@@ -27,10 +40,83 @@ pub struct Coord {
     pub y: u8,
 }
 
+/// One of the symmetries of a Hex board. Rotating the board by 180 degrees maps each player's edges
+/// onto themselves, so it preserves the color of every piece. Reflecting across either diagonal
+/// exchanges the left-right edges with the top-bottom edges, so the color of every piece must be
+/// swapped for the position to be equivalent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    /// Leaves every coordinate where it is.
+    Identity,
+    /// Rotates the board 180 degrees around its center, preserving colors.
+    Rotate180,
+    /// Reflects across the long diagonal running from the top left to the bottom right, swapping
+    /// colors. This is the reflection used by the swap rule.
+    ReflectLongDiagonal,
+    /// Reflects across the short diagonal running from the top right to the bottom left, swapping
+    /// colors.
+    ReflectShortDiagonal,
+}
+
+impl Symmetry {
+    /// Every symmetry of the board, starting with the identity.
+    pub fn all() -> [Symmetry; 4] {
+        [Symmetry::Identity, Symmetry::Rotate180, Symmetry::ReflectLongDiagonal, Symmetry::ReflectShortDiagonal]
+    }
+    /// Returns whether the symmetry exchanges the two players' edges, so that pieces must swap colors.
+    pub fn swaps_colors(self) -> bool {
+        match self {
+            Symmetry::Identity | Symmetry::Rotate180 => false,
+            Symmetry::ReflectLongDiagonal | Symmetry::ReflectShortDiagonal => true,
+        }
+    }
+}
+
+/// One of the six directions from a hex to its neighbors. Because rows are offset half a hex to
+/// the right as they go down, a hex's top left neighbor is directly above it in the same column,
+/// and its bottom right neighbor is directly below it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// Up one row, in the same column.
+    TopLeft,
+    /// Up one row and right one column.
+    TopRight,
+    /// Right one column, in the same row.
+    Right,
+    /// Down one row, in the same column.
+    BottomRight,
+    /// Down one row and left one column.
+    BottomLeft,
+    /// Left one column, in the same row.
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from the top left. This is the order `Coord::neighbors` uses.
+    pub fn all() -> [Direction; 6] {
+        [Direction::TopLeft, Direction::TopRight, Direction::Right,
+         Direction::BottomRight, Direction::BottomLeft, Direction::Left]
+    }
+    /// Returns the change in x and y from a hex to its neighbor in this direction.
+    pub fn offset(self) -> (i8, i8) {
+        match self {
+            Direction::TopLeft => (0, -1),
+            Direction::TopRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::BottomRight => (0, 1),
+            Direction::BottomLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
 impl Add<Coord> for Coord {
     type Output = Coord;
 
-    /// Adds componentwise, but does not check the addition.
+    /// Adds componentwise, but does not check the addition: this panics on overflow in debug builds,
+    /// and can produce coordinates past the largest possible board. Use `checked_add` to guard
+    /// against both.
     fn add(self, rhs: Coord) -> Self::Output {
         Coord {
             x: self.x + rhs.x,
@@ -39,13 +125,34 @@ impl Add<Coord> for Coord {
     }
 }
 
+impl Ord for Coord {
+    /// Orders coordinates in reading order: by row from the top, then by column from the left. This
+    /// matches the order of cells in the board display and in `Board::cells`.
+    fn cmp(&self, other: &Coord) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Coord {
+    fn partial_cmp(&self, other: &Coord) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Writes the coordinate as its column letter and 1-based row number, such as `c4`. A column past
+/// `z`, which no board can have but arithmetic on coordinates can produce, is written as `?` and
+/// its 0-based index followed by a colon instead, such as `?30:4`, so that formatting never panics.
 impl fmt::Display for Coord {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", ALPHABET.chars().nth(self.x as usize).unwrap(), self.y + 1)
+        let row = u16::from(self.y) + 1;
+        match ALPHABET.chars().nth(self.x as usize) {
+            Some(letter) => write!(f, "{}{}", letter, row),
+            None => write!(f, "?{}:{}", self.x, row),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// An error for parsing a `Coord`.
 pub enum ParseCoordError {
     InvalidFormat,
@@ -62,15 +169,9 @@ impl fmt::Display for ParseCoordError {
 }
 
 // This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
 impl error::Error for ParseCoordError {
-    fn description(&self) -> &str {
-        match *self {
-            ParseCoordError::InvalidFormat => "invalid coordinate string format",
-            ParseCoordError::InvalidInt(ref e) => e.description(),
-        }
-    }
-
-    fn cause(&self) -> Option<&error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             ParseCoordError::InvalidFormat => None,
             ParseCoordError::InvalidInt(ref e) => Some(e)
@@ -93,8 +194,9 @@ impl FromStr for Coord {
             Err(ParseCoordError::InvalidFormat)
         } else {
             let x: u8 = ALPHABET.find(|x| x == s_lower.chars().nth(0).unwrap()).unwrap() as u8;
-            let y: u8 = s_lower.chars().skip(1).collect::<String>().parse()?;            
-            match Coord::new(x, y - 1) {
+            let y: u8 = s_lower.chars().skip(1).collect::<String>().parse()?;
+            // rows are numbered from 1, so row 0 doesn't exist
+            match y.checked_sub(1).and_then(|y| Coord::new(x, y)) {
                 Some(c) => Ok(c),
                 None => Err(ParseCoordError::InvalidFormat)
             }
@@ -111,74 +213,193 @@ impl Coord {
             Option::Some(Coord{x, y})
         }
     }
-    /// Returns an iterator representing each of this hex's neighbors, clockwise from the top left. If
-    /// this hex is on the first row or column, will return fewer than six coordinates.
+    /// Parses a list of coordinates such as `"a1 c3, e5"`, separated by any mix of whitespace and
+    /// commas. Fails on the first coordinate that can't be parsed.
+    pub fn parse_list(s: &str) -> Result<Vec<Coord>, ParseCoordError> {
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(Coord::from_str)
+            .collect()
+    }
+    /// Returns each of this hex's neighbors, clockwise from the top left. If this hex is on an edge of
+    /// the largest possible (26x26) board, will return fewer than six coordinates: this never returns
+    /// a coordinate above 25. Because this doesn't know the actual board size, use `neighbors_within`
+    /// to exclude neighbors beyond the right and bottom edges of smaller boards.
     pub fn neighbors(self) -> Vec<Coord> {
-        if self == Coord::default() {
-            // hard-code: special case
-            return vec![
-                Coord{x: 1, y: 0},
-                Coord{x: 0, y: 1},
-            ]
-        }
-        else if self.x == 0 {
-            // on the left edge but not the left corner: four neighbors
-            return vec![
-                Coord{x: 0, y: self.y - 1},
-                Coord{x: 1, y: self.y - 1},
-                Coord{x: 1, y: self.y},
-                Coord{x: 0, y: self.y + 1}
-            ]
-        }
-        else if self.y == 0 {
-            // on the top edge but not the top corner: four neighbors
-            return vec![
-                Coord{x: self.x + 1, y: 0},
-                Coord{x: self.x, y: 1},
-                Coord{x: self.x - 1, y: 1},
-                Coord{x: self.x - 1, y: 0}
-            ]
-        }
-        else {
-            // return the six required vectors in order
-            vec![
-                // top left: (0, -1)
-                Coord{x: self.x, y: self.y - 1},
-                // top right: (1, -1)
-                Coord{x: self.x + 1, y: self.y - 1},
-                // right: (1, 0)
-                Coord{x: self.x + 1, y: self.y},
-                // bottom right: (0, 1)
-                Coord{x: self.x, y: self.y + 1},
-                // bottom left: (-1, 1)
-                Coord{x: self.x - 1, y: self.y + 1},
-                // left: (-1, 0)
-                Coord{x: self.x - 1, y: self.y},
-            ]
+        self.neighbors_within(26)
+    }
+    /// Returns each of this hex's neighbors that lie on a board of the given size, clockwise from the
+    /// top left. Hexes on any of the four edges will have fewer than six neighbors.
+    pub fn neighbors_within(self, size: u8) -> Vec<Coord> {
+        self.neighbors_iter(size).collect()
+    }
+    /// Returns an iterator over this hex's neighbors that lie on a board of the given size, clockwise
+    /// from the top left, like `neighbors_within` but without allocating.
+    pub fn neighbors_iter(self, size: u8) -> impl Iterator<Item = Coord> {
+        IntoIterator::into_iter(Direction::all()).filter_map(move |dir| self.neighbor_within(dir, size))
+    }
+    /// Returns this hex's neighbor in the given direction, or `None` if that would be off the largest
+    /// possible (26x26) board.
+    pub fn neighbor_at(self, dir: Direction) -> Option<Coord> {
+        self.neighbor_within(dir, 26)
+    }
+    /// Returns this hex's neighbor in the given direction, or `None` if that would be off a board of
+    /// the given size.
+    fn neighbor_within(self, dir: Direction, size: u8) -> Option<Coord> {
+        let (dx, dy) = dir.offset();
+        let (x, y) = (i16::from(self.x) + i16::from(dx), i16::from(self.y) + i16::from(dy));
+        if x >= 0 && y >= 0 && x < i16::from(size) && y < i16::from(size) {
+            Some(Coord{x: x as u8, y: y as u8})
+        } else {
+            None
         }
     }
     /// Returns true if the two hexes neighbor each other or equal each other, and false otherwise.
     pub fn is_neighbor(self, other: Coord) -> bool {
-        (Coord::abs_sub(self.x, other.x) <= 1 &&
-         Coord::abs_sub(self.y, other.y) <= 1 &&
-         Coord::abs_sub(self.x + self.y, other.x + other.y) <= 1)
+        self.distance(other) <= 1
     }
     /// Gets the absolute difference between two unsigned u8s, in a way that avoids overflow.
     pub fn abs_sub(int1: u8, int2: u8) -> u8 {
-        if int1 >= int2 {
-            int1 - int2
-        } else {
-            int2 - int1
-        }
+        int1.abs_diff(int2)
     }
     /// Gets the distance between two coordinates, defined as the number of steps in the grid needed
     /// to connect them. A distance of 0 means equality, and a distance of 1 means the two are
-    /// neighboring.
+    /// neighboring. This can't overflow, even for coordinates outside of any board, but saturates at
+    /// 255 for coordinates that are farther apart than that.
     pub fn distance(self, other: Coord) -> u8 {
-        // This is computed by realizing that this is really two axes of a 3D coordinate system
-        // representing the plane x + y + z = 0. Thus, what we do is just Manhattan distance in that
-        (Coord::abs_sub(self.x, other.x) + Coord::abs_sub(self.y, other.y) +
-         Coord::abs_sub(self.x + self.y, other.x + other.y)) / 2
+        // in cube coordinates, this is just half of the Manhattan distance; the i16 components can't
+        // overflow for any u8 inputs
+        let (q1, r1, s1) = self.to_cube();
+        let (q2, r2, s2) = other.to_cube();
+        let distance = ((q1 - q2).abs() + (r1 - r2).abs() + (s1 - s2).abs()) / 2;
+        distance.min(i16::from(u8::MAX)) as u8
+    }
+    /// Returns the hexes on the straight line from this hex to the other, including both ends, in
+    /// order: one more hex than the distance between them, each neighboring the next. This rounds
+    /// points along the line in cube coordinates to the nearest hex, as in the [Red Blob
+    /// Games](https://www.redblobgames.com/grids/hexagons/#line-drawing) guide, breaking ties the
+    /// same way every time.
+    pub fn line_to(self, other: Coord) -> Vec<Coord> {
+        let n = i32::from(self.distance(other));
+        if n == 0 {
+            return vec![self];
+        }
+        let (q1, r1, s1) = self.to_cube();
+        let (q2, r2, s2) = other.to_cube();
+        let start = [i32::from(q1), i32::from(r1), i32::from(s1)];
+        let delta = [i32::from(q2 - q1), i32::from(r2 - r1), i32::from(s2 - s1)];
+        // each point is scaled by 12n to keep to integers; the nudges sum to zero and are too small
+        // to change which hex a point rounds to, except to break ties
+        let unit = 12 * n;
+        let nudges = [1, 2, -3];
+        (0..=n).map(|i| {
+            let mut scaled = [0; 3];
+            let mut rounded = [0; 3];
+            let mut error = [0; 3];
+            for k in 0..3 {
+                scaled[k] = 12 * (start[k] * n + delta[k] * i) + nudges[k];
+                rounded[k] = (scaled[k] + unit / 2).div_euclid(unit);
+                error[k] = (scaled[k] - rounded[k] * unit).abs();
+            }
+            // rounding can leave the components not summing to zero, so the component that moved
+            // the most is recomputed from the other two
+            let [mut q, mut r, s] = rounded;
+            if error[0] > error[1] && error[0] > error[2] {
+                q = -r - s;
+            } else if error[1] > error[2] {
+                r = -q - s;
+            }
+            Coord{x: q as u8, y: r as u8}
+        }).collect()
+    }
+    /// Returns the hexes at exactly the given distance from this hex that lie on a board of the given
+    /// size, going clockwise from the one up and to the left. A radius of 0 gives just this hex, and
+    /// a radius of 1 gives the same hexes as `neighbors_within`, in the same order.
+    pub fn ring(self, radius: u8, size: u8) -> Vec<Coord> {
+        if radius == 0 {
+            return if self.x < size && self.y < size { vec![self] } else { vec![] };
+        }
+        let radius = i16::from(radius);
+        let (dx, dy) = Direction::TopLeft.offset();
+        let mut x = i16::from(self.x) + radius * i16::from(dx);
+        let mut y = i16::from(self.y) + radius * i16::from(dy);
+        let mut ring = vec![];
+        let sides = [Direction::Right, Direction::BottomRight, Direction::BottomLeft,
+                     Direction::Left, Direction::TopLeft, Direction::TopRight];
+        for &side in &sides {
+            let (dx, dy) = side.offset();
+            for _ in 0..radius {
+                if x >= 0 && y >= 0 && x < i16::from(size) && y < i16::from(size) {
+                    ring.push(Coord{x: x as u8, y: y as u8});
+                }
+                x += i16::from(dx);
+                y += i16::from(dy);
+            }
+        }
+        ring
+    }
+    /// Adds componentwise, returning `None` if either component overflows or the result is outside
+    /// the largest possible board.
+    pub fn checked_add(self, rhs: Coord) -> Option<Coord> {
+        Coord::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?)
+    }
+    /// Reflects this coordinate across the long diagonal of a board of the given size, swapping x
+    /// and y. The long diagonal runs from the top left corner to the bottom right, so this doesn't
+    /// actually depend on the size. The coordinate must be on the board.
+    pub fn reflect_long_diagonal(self, size: u8) -> Coord {
+        debug_assert!(self.x < size && self.y < size);
+        Coord{x: self.y, y: self.x}
+    }
+    /// Reflects this coordinate across the short diagonal of a board of the given size, which runs
+    /// from the top right corner to the bottom left. The coordinate must be on the board.
+    pub fn reflect_short_diagonal(self, size: u8) -> Coord {
+        debug_assert!(self.x < size && self.y < size);
+        Coord{x: size - 1 - self.y, y: size - 1 - self.x}
+    }
+    /// Rotates this coordinate 180 degrees around the center of a board of the given size. The
+    /// coordinate must be on the board.
+    pub fn rotate_180(self, size: u8) -> Coord {
+        debug_assert!(self.x < size && self.y < size);
+        Coord{x: size - 1 - self.x, y: size - 1 - self.y}
+    }
+    /// Applies the given symmetry of a board with the given size to this coordinate.
+    pub fn transform(self, symmetry: Symmetry, size: u8) -> Coord {
+        match symmetry {
+            Symmetry::Identity => self,
+            Symmetry::Rotate180 => self.rotate_180(size),
+            Symmetry::ReflectLongDiagonal => self.reflect_long_diagonal(size),
+            Symmetry::ReflectShortDiagonal => self.reflect_short_diagonal(size),
+        }
+    }
+    /// Gets the position of the center of this hex when drawn on screen, with x increasing to the
+    /// right and y increasing downwards. Hexes are drawn pointy-top, so that rows are horizontal and
+    /// each row is shifted half a hex to the right of the one above it, making the parallelogram
+    /// described above. `hex_size` is the width of each hex, which is also the distance between the
+    /// centers of neighboring hexes. The top left hex is placed so that the board's bounding box
+    /// starts at the origin.
+    pub fn to_pixel(self, hex_size: f64) -> (f64, f64) {
+        // the distance from the center of a hex to one of its corners
+        let radius = hex_size / SQRT_3;
+        let x = hex_size * (f64::from(self.x) + f64::from(self.y) / 2.0) + hex_size / 2.0;
+        let y = radius * 1.5 * f64::from(self.y) + radius;
+        (x, y)
+    }
+    /// Converts to [cube coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-cube)
+    /// `(q, r, s)`, where `q + r + s == 0`. The x-axis becomes `q` and the y-axis becomes `r`, so that
+    /// rotations and reflections around any hex are simple permutations and negations.
+    pub fn to_cube(self) -> (i16, i16, i16) {
+        let q = i16::from(self.x);
+        let r = i16::from(self.y);
+        (q, r, -q - r)
+    }
+    /// Converts from cube coordinates, the inverse of `to_cube`. Returns `None` if `q + r + s` isn't
+    /// zero or the coordinate would be outside the largest possible board.
+    pub fn from_cube(q: i16, r: i16, s: i16) -> Option<Coord> {
+        if q + r + s != 0 || q < 0 || r < 0 || q > 25 || r > 25 {
+            None
+        } else {
+            Some(Coord{x: q as u8, y: r as u8})
+        }
     }
 }
 
@@ -186,6 +407,7 @@ impl Coord {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn test_constructor() {
@@ -216,6 +438,58 @@ mod tests {
                         Coord{x: 6, y: 5}]);
     }
     #[test]
+    fn test_neighbor_at() {
+        let center = Coord{x: 7, y: 5};
+        let neighbors: Vec<Coord> = Direction::all().iter().filter_map(|&dir| center.neighbor_at(dir)).collect();
+        assert_eq!(neighbors, center.neighbors());
+        assert_eq!(center.neighbor_at(Direction::BottomLeft), Some(Coord{x: 6, y: 6}));
+        assert_eq!(Coord{x: 0, y: 3}.neighbor_at(Direction::Left), None);
+        assert_eq!(Coord{x: 25, y: 3}.neighbor_at(Direction::TopRight), None);
+        assert_eq!(Coord{x: 4, y: 4}.neighbors_iter(5).collect::<Vec<_>>(), Coord{x: 4, y: 4}.neighbors_within(5));
+    }
+    #[test]
+    fn test_direction_offsets() {
+        let center = Coord{x: 7, y: 5};
+        let neighbors: Vec<Coord> = Direction::all().iter().map(|dir| {
+            let (dx, dy) = dir.offset();
+            Coord{x: (i16::from(center.x) + i16::from(dx)) as u8, y: (i16::from(center.y) + i16::from(dy)) as u8}
+        }).collect();
+        assert_eq!(neighbors, center.neighbors());
+        // opposite directions cancel out
+        for (i, dir) in Direction::all().iter().enumerate() {
+            let (dx, dy) = dir.offset();
+            let (ox, oy) = Direction::all()[(i + 3) % 6].offset();
+            assert_eq!((dx + ox, dy + oy), (0, 0));
+        }
+    }
+    #[test]
+    fn test_neighbors_within() {
+        assert_eq!(Coord{x: 0, y: 0}.neighbors_within(13),
+                   vec![Coord{x: 1, y: 0},
+                        Coord{x: 0, y: 1}]);
+        assert_eq!(Coord{x: 12, y: 0}.neighbors_within(13),
+                   vec![Coord{x: 12, y: 1},
+                        Coord{x: 11, y: 1},
+                        Coord{x: 11, y: 0}]);
+        assert_eq!(Coord{x: 0, y: 12}.neighbors_within(13),
+                   vec![Coord{x: 0, y: 11},
+                        Coord{x: 1, y: 11},
+                        Coord{x: 1, y: 12}]);
+        assert_eq!(Coord{x: 12, y: 12}.neighbors_within(13),
+                   vec![Coord{x: 12, y: 11},
+                        Coord{x: 11, y: 12}]);
+        assert_eq!(Coord{x: 7, y: 5}.neighbors_within(13), Coord{x: 7, y: 5}.neighbors());
+    }
+    #[test]
+    fn test_neighbors_saturate() {
+        assert_eq!(Coord{x: 25, y: 5}.neighbors(),
+                   vec![Coord{x: 25, y: 4},
+                        Coord{x: 25, y: 6},
+                        Coord{x: 24, y: 6},
+                        Coord{x: 24, y: 5}]);
+        assert!(Coord{x: 25, y: 25}.neighbors().iter().all(|c| c.x <= 25 && c.y <= 25));
+    }
+    #[test]
     fn test_is_neighbor() {
         assert!(Coord{x: 0, y: 0}.is_neighbor(Coord{x: 0, y: 1}));
         assert!(Coord{x: 0, y: 0}.is_neighbor(Coord{x: 1, y: 0}));
@@ -235,6 +509,93 @@ mod tests {
         assert_eq!(Coord{x: 4, y: 3}.distance(Coord{x: 1, y: 1}), 5);
         assert_eq!(Coord{x: 4, y: 3}.distance(Coord{x: 4, y: 2}), 1);
         assert_eq!(Coord{x: 4, y: 3}.distance(Coord{x: 4, y: 3}), 0);
+        // none of these overflow, even though x + y doesn't fit in a u8
+        assert_eq!(Coord{x: 200, y: 200}.distance(Coord{x: 199, y: 201}), 1);
+        assert_eq!(Coord{x: 255, y: 255}.distance(Coord{x: 255, y: 0}), 255);
+        assert_eq!(Coord{x: 255, y: 255}.distance(Coord{x: 0, y: 0}), 255);
+        assert!(Coord{x: 255, y: 254}.is_neighbor(Coord{x: 254, y: 255}));
+    }
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(Coord{x: 3, y: 4}.checked_add(Coord{x: 1, y: 2}), Some(Coord{x: 4, y: 6}));
+        assert_eq!(Coord{x: 24, y: 0}.checked_add(Coord{x: 1, y: 25}), Some(Coord{x: 25, y: 25}));
+        assert_eq!(Coord{x: 25, y: 0}.checked_add(Coord{x: 1, y: 0}), None);
+        assert_eq!(Coord{x: 0, y: 25}.checked_add(Coord{x: 0, y: 1}), None);
+        assert_eq!(Coord{x: 255, y: 0}.checked_add(Coord{x: 1, y: 0}), None);
+        assert_eq!(Coord{x: 0, y: 200}.checked_add(Coord{x: 0, y: 100}), None);
+    }
+
+    #[test]
+    fn test_cube() {
+        for &(x, y) in &[(0, 0), (3, 0), (0, 7), (12, 5), (25, 25)] {
+            let c = Coord{x, y};
+            let (q, r, s) = c.to_cube();
+            assert_eq!(q + r + s, 0);
+            assert_eq!(Coord::from_cube(q, r, s), Some(c));
+        }
+        assert_eq!(Coord{x: 4, y: 3}.to_cube(), (4, 3, -7));
+        assert_eq!(Coord::from_cube(1, 1, 1), None);
+        assert_eq!(Coord::from_cube(-1, 2, -1), None);
+        assert_eq!(Coord::from_cube(26, 0, -26), None);
+    }
+
+    #[test]
+    fn test_line_to() {
+        let start = Coord{x: 0, y: 0};
+        assert_eq!(start.line_to(start), vec![start]);
+        assert_eq!(start.line_to(Coord{x: 3, y: 0}), (0..4).map(|x| Coord{x, y: 0}).collect::<Vec<_>>());
+        assert_eq!(Coord{x: 4, y: 0}.line_to(Coord{x: 0, y: 4}),
+                   (0..5).map(|i| Coord{x: 4 - i, y: i}).collect::<Vec<_>>());
+        // a line with ties between two hexes at every other step
+        for &(a, b) in &[(start, Coord{x: 2, y: 2}), (Coord{x: 7, y: 1}, Coord{x: 1, y: 10})] {
+            let line = a.line_to(b);
+            assert_eq!(line.len(), usize::from(a.distance(b)) + 1);
+            assert_eq!((line[0], line[line.len() - 1]), (a, b));
+            assert!(line.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+            assert_eq!(b.line_to(a).len(), line.len());
+        }
+    }
+
+    #[test]
+    fn test_ring() {
+        let center = Coord{x: 2, y: 2};
+        assert_eq!(center.ring(1, 5), center.neighbors_within(5));
+        assert_eq!(center.ring(0, 5), vec![center]);
+        let ring = center.ring(2, 5);
+        assert_eq!(ring.len(), 12);
+        assert!(ring.iter().all(|&c| c.distance(center) == 2));
+        // the corner only has two of its six neighbors on the board
+        assert_eq!(Coord{x: 0, y: 0}.ring(1, 5), Coord{x: 0, y: 0}.neighbors_within(5));
+        assert_eq!(Coord{x: 0, y: 0}.ring(2, 5).len(), 3);
+        assert!(center.ring(3, 5).iter().all(|&c| c.distance(center) == 3));
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let c = Coord{x: 1, y: 3};
+        assert_eq!(c.reflect_long_diagonal(5), Coord{x: 3, y: 1});
+        assert_eq!(c.reflect_short_diagonal(5), Coord{x: 1, y: 3});
+        assert_eq!(Coord{x: 0, y: 0}.reflect_short_diagonal(5), Coord{x: 4, y: 4});
+        assert_eq!(c.rotate_180(5), Coord{x: 3, y: 1});
+        assert_eq!(Coord{x: 4, y: 0}.rotate_180(5), Coord{x: 0, y: 4});
+        for symmetry in Symmetry::all().iter() {
+            // every symmetry is its own inverse
+            assert_eq!(c.transform(*symmetry, 5).transform(*symmetry, 5), c);
+        }
+    }
+
+    #[test]
+    fn test_to_pixel() {
+        let c = Coord{x: 3, y: 4};
+        let (x, y) = c.to_pixel(10.0);
+        for neighbor in c.neighbors() {
+            let (nx, ny) = neighbor.to_pixel(10.0);
+            let distance = ((nx - x).powi(2) + (ny - y).powi(2)).sqrt();
+            assert!((distance - 10.0).abs() < 1e-9);
+        }
+        let (x0, y0) = Coord{x: 0, y: 0}.to_pixel(10.0);
+        assert!((x0 - 5.0).abs() < 1e-9);
+        assert!((y0 - 10.0 / SQRT_3).abs() < 1e-9);
     }
 
     #[test]
@@ -244,14 +605,69 @@ mod tests {
         assert_eq!(&Coord{x: 13, y: 5}.to_string(), "n6");
         assert_eq!(&Coord{x: 25, y: 25}.to_string(), "z26");
         assert_eq!(&Coord{x: 25, y: 0}.to_string(), "z1");
+        // coordinates off the largest board don't panic
+        assert_eq!(&Coord{x: 30, y: 0}.to_string(), "?30:1");
+        assert_eq!(&Coord{x: 255, y: 255}.to_string(), "?255:256");
     }
 
     #[test]
     fn test_parsing() {
-        for string in vec!["a1", "a16", "B6", "z6", "z23", "Q25"].iter() {
+        for string in ["a1", "a16", "B6", "z6", "z23", "Q25"].iter() {
             assert_eq!(string.to_lowercase(), Coord::from_str(string).unwrap().to_string());
         }
         assert!(Coord::from_str("ZZ").is_err());
         assert!(Coord::from_str("Z126").is_err());
+        assert_eq!(Coord::from_str("a0"), Err(ParseCoordError::InvalidFormat));
+        assert_eq!(Coord::from_str("a26"), Ok(Coord{x: 0, y: 25}));
+        assert_eq!(Coord::from_str("a27"), Err(ParseCoordError::InvalidFormat));
+        assert_eq!(Coord::from_str("a255"), Err(ParseCoordError::InvalidFormat));
+        assert!(matches!(Coord::from_str("a256"), Err(ParseCoordError::InvalidInt(_))));
+        assert!(Coord::from_str("a-1").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_error_source() {
+        use std::error::Error;
+        let err = Coord::from_str("bx").unwrap_err();
+        let inner = "x".parse::<u8>().unwrap_err();
+        assert_eq!(err, ParseCoordError::InvalidInt(inner.clone()));
+        let source = err.source().unwrap().downcast_ref::<ParseIntError>();
+        assert_eq!(source, Some(&inner));
+        assert!(ParseCoordError::InvalidFormat.source().is_none());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut coords = vec![Coord{x: 2, y: 1}, Coord{x: 0, y: 2}, Coord{x: 1, y: 0},
+                              Coord{x: 0, y: 1}, Coord{x: 5, y: 0}];
+        coords.sort();
+        assert_eq!(coords, vec![Coord{x: 1, y: 0}, Coord{x: 5, y: 0}, Coord{x: 0, y: 1},
+                                Coord{x: 2, y: 1}, Coord{x: 0, y: 2}]);
+        assert!(Coord{x: 25, y: 0} < Coord{x: 0, y: 1});
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(Coord::parse_list("A1, b2 c3").unwrap(),
+                   vec![Coord{x: 0, y: 0}, Coord{x: 1, y: 1}, Coord{x: 2, y: 2}]);
+        assert_eq!(Coord::parse_list(" d10,,e4\n").unwrap(), vec![Coord{x: 3, y: 9}, Coord{x: 4, y: 3}]);
+        assert_eq!(Coord::parse_list("").unwrap(), vec![]);
+        assert!(Coord::parse_list("a1 b2 ?3").is_err());
+    }
+
+    #[test]
+    fn test_alphabet_order() {
+        // i and j were once swapped in the alphabet, corrupting columns 8 and 9
+        assert_eq!(&Coord{x: 8, y: 0}.to_string(), "i1");
+        assert_eq!(&Coord{x: 9, y: 0}.to_string(), "j1");
+    }
+
+    #[test]
+    fn test_round_trip_columns() {
+        for x in 0..26 {
+            let c = Coord{x, y: 0};
+            assert_eq!(Coord::from_str(&c.to_string()), Ok(c));
+        }
     }
 }
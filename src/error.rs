@@ -0,0 +1,174 @@
+//! A single error type covering every error in this crate, for callers that parse, load, and play
+//! games in one place and want to pass any of the errors on with `?`.
+
+use std::error;
+use std::fmt;
+
+use crate::binary::DecodeError;
+use crate::board::{BoardSizeError, EmptyCellError, ParseBoardError};
+use crate::coord::ParseCoordError;
+use crate::game::{MoveError, ReplayError};
+use crate::gamemetadata::DateError;
+use crate::openingbook::OpeningBookError;
+use crate::sgf::SgfError;
+use crate::trmph::TrmphError;
+
+/// Any of the errors in this crate. Each variant wraps the more specific error, which is also its
+/// `source`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HexError {
+    /// A board size was out of range.
+    BoardSize(BoardSizeError),
+    /// An empty cell was converted into a color.
+    EmptyCell(EmptyCellError),
+    /// A coordinate couldn't be parsed.
+    ParseCoord(ParseCoordError),
+    /// A board diagram couldn't be parsed.
+    ParseBoard(ParseBoardError),
+    /// A move couldn't be made.
+    Move(MoveError),
+    /// A list of moves couldn't be replayed.
+    Replay(ReplayError),
+    /// A date doesn't exist on the calendar.
+    Date(DateError),
+    /// An SGF record couldn't be read.
+    Sgf(SgfError),
+    /// A trmph record couldn't be read.
+    Trmph(TrmphError),
+    /// An opening book couldn't be loaded.
+    OpeningBook(OpeningBookError),
+    /// A game in the binary format couldn't be read.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HexError::BoardSize(ref e) => e.fmt(f),
+            HexError::EmptyCell(ref e) => e.fmt(f),
+            HexError::ParseCoord(ref e) => e.fmt(f),
+            HexError::ParseBoard(ref e) => e.fmt(f),
+            HexError::Move(ref e) => e.fmt(f),
+            HexError::Replay(ref e) => e.fmt(f),
+            HexError::Date(ref e) => e.fmt(f),
+            HexError::Sgf(ref e) => e.fmt(f),
+            HexError::Trmph(ref e) => e.fmt(f),
+            HexError::OpeningBook(ref e) => e.fmt(f),
+            HexError::Decode(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for HexError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            HexError::BoardSize(ref e) => Some(e),
+            HexError::EmptyCell(ref e) => Some(e),
+            HexError::ParseCoord(ref e) => Some(e),
+            HexError::ParseBoard(ref e) => Some(e),
+            HexError::Move(ref e) => Some(e),
+            HexError::Replay(ref e) => Some(e),
+            HexError::Date(ref e) => Some(e),
+            HexError::Sgf(ref e) => Some(e),
+            HexError::Trmph(ref e) => Some(e),
+            HexError::OpeningBook(ref e) => Some(e),
+            HexError::Decode(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<BoardSizeError> for HexError {
+    fn from(err: BoardSizeError) -> HexError {
+        HexError::BoardSize(err)
+    }
+}
+
+impl From<EmptyCellError> for HexError {
+    fn from(err: EmptyCellError) -> HexError {
+        HexError::EmptyCell(err)
+    }
+}
+
+impl From<ParseCoordError> for HexError {
+    fn from(err: ParseCoordError) -> HexError {
+        HexError::ParseCoord(err)
+    }
+}
+
+impl From<ParseBoardError> for HexError {
+    fn from(err: ParseBoardError) -> HexError {
+        HexError::ParseBoard(err)
+    }
+}
+
+impl From<MoveError> for HexError {
+    fn from(err: MoveError) -> HexError {
+        HexError::Move(err)
+    }
+}
+
+impl From<ReplayError> for HexError {
+    fn from(err: ReplayError) -> HexError {
+        HexError::Replay(err)
+    }
+}
+
+impl From<DateError> for HexError {
+    fn from(err: DateError) -> HexError {
+        HexError::Date(err)
+    }
+}
+
+impl From<SgfError> for HexError {
+    fn from(err: SgfError) -> HexError {
+        HexError::Sgf(err)
+    }
+}
+
+impl From<TrmphError> for HexError {
+    fn from(err: TrmphError) -> HexError {
+        HexError::Trmph(err)
+    }
+}
+
+impl From<OpeningBookError> for HexError {
+    fn from(err: OpeningBookError) -> HexError {
+        HexError::OpeningBook(err)
+    }
+}
+
+impl From<DecodeError> for HexError {
+    fn from(err: DecodeError) -> HexError {
+        HexError::Decode(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use std::error::Error;
+    use crate::board::HexCell;
+    use crate::coord::Coord;
+    use crate::game::Game;
+
+    /// Loads an SGF record and plays the given move on it, failing with whichever error comes first.
+    fn load_and_play(sgf: &str, coord: &str) -> Result<Game, HexError> {
+        let (mut game, _) = Game::from_sgf(sgf)?;
+        let coord: Coord = coord.parse()?;
+        game.try_move(coord)?;
+        Ok(game)
+    }
+
+    #[test]
+    fn test_question_mark() {
+        assert!(load_and_play("(;GM[11]SZ[5];B[cc])", "a1").is_ok());
+        assert_eq!(load_and_play("(;GM[11]SZ[5]", "a1").unwrap_err(), HexError::Sgf(SgfError::InvalidFormat));
+        assert_eq!(load_and_play("(;GM[11]SZ[5];B[cc])", "!!").unwrap_err(),
+                   HexError::ParseCoord(ParseCoordError::InvalidFormat));
+        let err = load_and_play("(;GM[11]SZ[5];B[cc])", "c3").unwrap_err();
+        assert_eq!(err, HexError::Move(MoveError::Occupied(HexCell::Black)));
+        assert_eq!(err.to_string(), MoveError::Occupied(HexCell::Black).to_string());
+        assert!(err.source().is_some());
+    }
+}
@@ -0,0 +1,109 @@
+//! A C interface to `Game`, for driving games from C, Swift, or anything else that can call C
+//! functions. Enabled by the `ffi` feature.
+//!
+//! Games are handed out as opaque pointers. A pointer returned by `hex_game_new` is owned by the
+//! caller, who must pass it to `hex_game_free` exactly once when done with it, and must not use it
+//! afterwards. Every function accepts a null pointer, treating it as a game on which nothing can be
+//! done.
+
+use std::ptr;
+
+use crate::board::GameStatus;
+use crate::coord::Coord;
+use crate::game::Game;
+
+/// The status returned by `hex_game_status` while neither player has won.
+pub const HEX_GAME_ONGOING: i32 = 0;
+/// The status returned by `hex_game_status` once Black has won.
+pub const HEX_GAME_BLACK_WIN: i32 = 1;
+/// The status returned by `hex_game_status` once White has won.
+pub const HEX_GAME_WHITE_WIN: i32 = 2;
+/// The status returned by `hex_game_status` for a null pointer.
+pub const HEX_GAME_NULL: i32 = -1;
+
+/// Creates a new game of the given size, returning a pointer the caller owns and must free with
+/// `hex_game_free`, or null if the size is 0 or larger than 26.
+#[no_mangle]
+pub extern "C" fn hex_game_new(size: u8) -> *mut Game {
+    match Game::try_new(size) {
+        Ok(game) => Box::into_raw(Box::new(game)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Plays the next player's piece at the given column and row, both counting from 0, returning
+/// whether the move was made. Returns false without doing anything if the pointer is null, the cell
+/// is out of bounds or occupied, or the game is over.
+///
+/// # Safety
+///
+/// The pointer must be null or a game from `hex_game_new` that hasn't been freed, and no other
+/// thread can be using the game at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn hex_game_make_move(game: *mut Game, x: u8, y: u8) -> bool {
+    match game.as_mut() {
+        Some(game) => game.try_move(Coord{x, y}).is_ok(),
+        None => false,
+    }
+}
+
+/// Returns the status of the game: `HEX_GAME_ONGOING`, `HEX_GAME_BLACK_WIN`, or
+/// `HEX_GAME_WHITE_WIN`, or `HEX_GAME_NULL` if the pointer is null.
+///
+/// # Safety
+///
+/// The pointer must be null or a game from `hex_game_new` that hasn't been freed, and no other
+/// thread can be modifying the game at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn hex_game_status(game: *const Game) -> i32 {
+    match game.as_ref().map(Game::status) {
+        Some(GameStatus::Ongoing) => HEX_GAME_ONGOING,
+        Some(GameStatus::BlackWin) => HEX_GAME_BLACK_WIN,
+        Some(GameStatus::WhiteWin) => HEX_GAME_WHITE_WIN,
+        None => HEX_GAME_NULL,
+    }
+}
+
+/// Frees a game created by `hex_game_new`. Does nothing if the pointer is null.
+///
+/// # Safety
+///
+/// The pointer must be null or a game from `hex_game_new` that hasn't already been freed. It can't
+/// be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn hex_game_free(game: *mut Game) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_game_lifecycle() {
+        assert!(hex_game_new(0).is_null());
+        assert!(hex_game_new(27).is_null());
+        let game = hex_game_new(2);
+        assert!(!game.is_null());
+        unsafe {
+            assert_eq!(hex_game_status(game), HEX_GAME_ONGOING);
+            assert!(hex_game_make_move(game, 0, 1));
+            // occupied and out of bounds
+            assert!(!hex_game_make_move(game, 0, 1));
+            assert!(!hex_game_make_move(game, 2, 0));
+            assert!(hex_game_make_move(game, 0, 0));
+            assert!(hex_game_make_move(game, 1, 0));
+            assert_eq!(hex_game_status(game), HEX_GAME_BLACK_WIN);
+            // the game is over
+            assert!(!hex_game_make_move(game, 1, 1));
+            hex_game_free(game);
+
+            assert!(!hex_game_make_move(ptr::null_mut(), 0, 0));
+            assert_eq!(hex_game_status(ptr::null()), HEX_GAME_NULL);
+            hex_game_free(ptr::null_mut());
+        }
+    }
+}
@@ -1,14 +1,72 @@
 //! This file's `Game` struct represents a game of Hex.
 
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use colored::Colorize;
+#[cfg(feature = "rand")]
+use rand::seq::SliceRandom;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::board::{Board, Color, GameStatus, HexCell};
+use crate::board::{Board, BoardSizeError, Color, GameStatus, HexCell};
 use crate::coord::Coord;
+use crate::gamemetadata::GameEnding;
+
+/// A single move in a game of Hex: either placing a stone, or swapping colors as White's first move
+/// according to the swap (pie) rule.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Move {
+    /// Places a stone of the current player's color at the coordinate.
+    Place(Coord),
+    /// Reflects Black's opening stone across the long diagonal into a White stone, which is the
+    /// same as the players exchanging colors.
+    Swap,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Move::Place(coord) => write!(f, "{}", coord),
+            Move::Swap => write!(f, "swap"),
+        }
+    }
+}
+
+impl From<Coord> for Move {
+    fn from(coord: Coord) -> Move {
+        Move::Place(coord)
+    }
+}
+
+/// Notes on a single move, for teaching and analysis: a comment, and whether the move is good or
+/// bad. These are written to SGF as the move's `C`, `TE`, and `BM` properties.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MoveAnnotation {
+    /// A comment on the move, or an empty string if there isn't one.
+    pub comment: String,
+    /// Whether the move is a good one.
+    pub good: bool,
+    /// Whether the move is a bad one.
+    pub bad: bool,
+}
+
+impl MoveAnnotation {
+    /// Returns whether the annotation has no comment and no marks, which is the same as not having
+    /// an annotation.
+    pub fn is_empty(&self) -> bool {
+        self.comment.is_empty() && !self.good && !self.bad
+    }
+}
 
 /// A game of Hex, with move history. Metadata about the game (players, ratings, etc.) comes from a
-/// `GameMetadata` struct: this simply captures the actual moves and whether the players swapped.
+/// `GameMetadata` struct: this simply captures the actual moves, including any swap.
 ///
 /// Although colors differ between Hex implementations, this crate consistently has Black as the
 /// player that moves first and who tries to connect the left and right sides, on a game board where
@@ -19,49 +77,314 @@ pub struct Game {
     /// than 26x26.
     pub board_size: u8,
     /// A list of moves, such that Black goes first and on every other odd-numbered move, and White
-    /// goes on every even-numbered moves. If the White player swaps on their first move, that should
-    /// be indicated by the game's metadata: in `GameMetadata`, Black and White are the players as
-    /// they were at the end of the game, not as at the beginning.
+    /// goes on every even-numbered move. If the White player swaps on their first move, that is
+    /// recorded as a `Move::Swap` in the second slot, and after that Black's opening stone counts as
+    /// White's. In `GameMetadata`, Black and White are the players as they were at the end of the
+    /// game, not as at the beginning.
     ///
     /// If the list of moves is invalid for whatever reason (out-of-bounds coordinates, playing to the
-    /// same square twice, etc.), undefined behavior, including possible panics, can result.
-    pub moves: Vec<Coord>,
+    /// same square twice, a swap anywhere other than the second move, etc.), undefined behavior,
+    /// including possible panics, can result. Use `from_moves` to safely create a game from a list
+    /// of moves that might be invalid.
+    pub moves: Vec<Move>,
     /// The current board, given the above moves.
     board: Board,
+    /// How the game ended, if a player resigned instead of losing on the board.
+    ending: GameEnding,
+    /// The annotation of each move, in the same order as the moves. This stops after the last
+    /// annotated move, so it can be shorter than the list of moves.
+    annotations: Vec<MoveAnnotation>,
+}
+
+/// An error for making an invalid move.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MoveError {
+    /// The coordinate is outside of the board.
+    OutOfBounds,
+    /// The cell already has the given piece in it.
+    Occupied(HexCell),
+    /// The game has already been won, with the given status.
+    GameAlreadyOver(GameStatus),
+    /// A swap was attempted when it isn't White's first move.
+    InvalidSwap,
+    /// The move was given as text that isn't a valid coordinate.
+    InvalidNotation,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MoveError::OutOfBounds => write!(f, "move is out of bounds"),
+            MoveError::Occupied(cell) => write!(f, "cell is already occupied by {:?}", cell),
+            MoveError::GameAlreadyOver(status) => write!(f, "game is already over: {:?}", status),
+            MoveError::InvalidSwap => write!(f, "swapping is only allowed as White's first move"),
+            MoveError::InvalidNotation => write!(f, "move is not a valid coordinate"),
+        }
+    }
+}
+
+impl error::Error for MoveError {}
+
+/// How a finished game was won.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WinKind {
+    /// The winner connected their edges on the board.
+    Connection,
+    /// The loser resigned.
+    Resignation,
+    /// The loser forfeited, by time loss, disqualification, or the like.
+    Forfeit,
+}
+
+/// The result of a finished game: who won, how, and with which move.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Outcome {
+    /// The player who won.
+    pub winner: Color,
+    /// The move that completed the winner's connection, or `None` if the game ended by resignation
+    /// or forfeit.
+    pub deciding_move: Option<Coord>,
+    /// How the game was won.
+    pub by: WinKind,
+}
+
+/// An error for replaying a list of moves: either the board size is invalid, or one of the moves
+/// couldn't be made.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ReplayError {
+    /// The board size is 0 or larger than 26.
+    BoardSize(BoardSizeError),
+    /// The first move that couldn't be made.
+    Move {
+        /// The index of the invalid move in the list.
+        index: usize,
+        /// Why that move couldn't be made.
+        error: MoveError,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::BoardSize(ref error) => error.fmt(f),
+            ReplayError::Move { index, error } => write!(f, "invalid move at index {}: {}", index, error),
+        }
+    }
+}
+
+impl error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ReplayError::BoardSize(ref error) => Some(error),
+            ReplayError::Move { ref error, .. } => Some(error),
+        }
+    }
+}
+
+/// Returns the number of move pairs started after the given number of moves, counting the next move,
+/// as recorded in the resignations and forfeits of `GameEnding`. Those only hold a `u8`, so this
+/// stops at 255 for games of more than 508 moves, which only happen on boards of 23x23 and larger.
+pub(crate) fn ending_move_number(moves: usize) -> u8 {
+    let number = moves / 2 + 1;
+    if number > usize::from(u8::MAX) { u8::MAX } else { number as u8 }
 }
 
 impl fmt::Display for Game {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // uses a numbering system, as is common in Hex
-        let mut numbered_board = vec![];
-        // initialize board as blank
-        for _x in 0..self.board_size {
-            for _y in 0..self.board_size {
-                // push two dots so that you have enough room for 99 moves
-                numbered_board.push("⋅⋅".to_string());
+        write!(f, "{}", self.numbered_diagram(true))
+    }
+}
+
+/// The information needed to reconstruct a `Game`, used for serialization so that the board's
+/// internal union-find structure doesn't need to be stored. The swap is stored as a flag rather
+/// than in the list of moves, so that records written before swaps were moves can still be read.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct GameRecord {
+    board_size: u8,
+    moves: Vec<Coord>,
+    swapped: bool,
+    #[serde(default)]
+    ending: GameEnding,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GameRecord {
+            board_size: self.board_size,
+            moves: self.moves.iter().filter_map(|&m| match m {
+                Move::Place(coord) => Some(coord),
+                Move::Swap => None,
+            }).collect(),
+            swapped: self.swapped(),
+            ending: self.ending,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Game {
+    /// Deserializes the board size, moves, and swap, then replays the moves to rebuild the board.
+    /// Fails if any of the moves are invalid.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GameRecord::deserialize(deserializer)?;
+        let mut game = Game::try_new(record.board_size).map_err(de::Error::custom)?;
+        for (i, coord) in record.moves.into_iter().enumerate() {
+            if let Err(e) = game.try_move(coord) {
+                return Err(de::Error::custom(format!("invalid move {} at index {}: {}", coord, i, e)));
+            }
+            if i == 0 && record.swapped {
+                game.swap();
+            }
+        }
+        if record.swapped && !game.swapped() {
+            return Err(de::Error::custom("swap without an opening move"));
+        }
+        if record.ending != GameEnding::NotApplicable && game.status() != GameStatus::Ongoing {
+            return Err(de::Error::custom("resignation after the game was won"));
+        }
+        game.ending = record.ending;
+        Ok(game)
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        // 13 is default size
+        Game::new(13)
+    }
+}
+
+/// Games are equal if they have the same size and the same moves, including whether White swapped.
+/// The board follows from those and isn't compared, and neither are resignations, forfeits, or
+/// annotations, so a game is the same however it ended.
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        self.board_size == other.board_size && self.moves == other.moves
+    }
+}
+
+impl Eq for Game {}
+
+impl Hash for Game {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.board_size.hash(state);
+        self.moves.hash(state);
+    }
+}
+
+/// Copies the game's current position into a standalone board. This clones the board's piece sets
+/// and union-find structures, so it takes time and memory proportional to the size of the board:
+/// use `Game::board` to just look at the position.
+impl From<&Game> for Board {
+    fn from(game: &Game) -> Board {
+        game.board.clone()
+    }
+}
+
+impl Game {
+    /// Returns a new Game of the given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or larger than 26. Use `try_new` to handle invalid sizes instead.
+    pub fn new(size: u8) -> Game {
+        match Game::try_new(size) {
+            Ok(game) => game,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    /// Returns a new Game of the given size, or an error if the size is 0 or larger than 26.
+    pub fn try_new(size: u8) -> Result<Game, BoardSizeError> {
+        Ok(Game {
+            board_size: size,
+            board: Board::try_new(u16::from(size))?,
+            moves: vec![],
+            ending: GameEnding::NotApplicable,
+            annotations: vec![],
+        })
+    }
+    /// Returns a new Game of the given size with the given moves played, swapping after the first
+    /// move if `swapped` is true. Each move is checked, and the first invalid one is returned as an
+    /// error along with its index in `moves`. The swap, if any, isn't listed in `moves`. Returns an
+    /// error instead if the size is 0 or larger than 26.
+    pub fn from_moves(size: u8, moves: &[Coord], swapped: bool) -> Result<Game, ReplayError> {
+        if moves.len() < 3 {
+            return Game::replay(size, moves, swapped);
+        }
+        // the opening and the swap are played as usual, then the moves before the last are placed
+        // without checking for a win after each one; if anything goes wrong, or someone won before
+        // the last move, replaying one move at a time finds the move that was invalid
+        let mut game = Game::replay(size, &moves[..1], swapped)?;
+        let (&last, middle) = moves[1..].split_last().unwrap();
+        for &coord in middle {
+            let color = game.next_move_color();
+            if !game.board.place_piece_deferred(coord, color) {
+                return Game::replay(size, moves, swapped);
             }
+            game.moves.push(Move::Place(coord));
         }
+        if game.board.refresh_status() != GameStatus::Ongoing {
+            return Game::replay(size, moves, swapped);
+        }
+        game.try_move(last).map_err(|error| ReplayError::Move { index: moves.len() - 1, error })?;
+        Ok(game)
+    }
+    /// Does the same as `from_moves`, but plays each move in turn, checking for a win after each.
+    fn replay(size: u8, moves: &[Coord], swapped: bool) -> Result<Game, ReplayError> {
+        let mut game = Game::try_new(size).map_err(ReplayError::BoardSize)?;
+        for (index, &coord) in moves.iter().enumerate() {
+            game.try_move(coord).map_err(|error| ReplayError::Move { index, error })?;
+            if index == 0 && swapped {
+                game.swap();
+            }
+        }
+        if swapped && moves.is_empty() {
+            return Err(ReplayError::Move { index: 0, error: MoveError::InvalidSwap });
+        }
+        Ok(game)
+    }
+    /// Returns the number, location, and color of the stone placed by each move that places one, in
+    /// order. A swapped opening stone is White's and sits at its reflection.
+    fn stones(&self) -> impl Iterator<Item = (u16, Coord, Color)> + '_ {
+        let swapped = self.swapped();
+        (1u16..).zip(&self.moves).filter_map(move |(num, m)| match *m {
+            Move::Place(coord) if swapped && num == 1 => {
+                Some((num, coord.reflect_long_diagonal(self.board_size), Color::White))
+            }
+            Move::Place(coord) if num % 2 == 1 => Some((num, coord, Color::Black)),
+            Move::Place(coord) => Some((num, coord, Color::White)),
+            Move::Swap => None,
+        })
+    }
+    /// Draws the board with each stone labeled by the number of the move that placed it, as is
+    /// common in Hex. If `colored` is true, each number is highlighted with the color of its stone
+    /// using ANSI escape codes.
+    fn numbered_diagram(&self, colored: bool) -> String {
+        let size = usize::from(self.board_size);
+        // initialize board as blank, with two dots so that you have enough room for 99 moves
+        let mut numbered_board = vec!["⋅⋅".to_string(); size * size];
         // now go through each move and modify the corresponding number
-        let mut curr_num = 1;  // first move is numbered 1, not 0
-        for coord in &self.moves {
+        // first move is numbered 1, not 0
+        for (curr_num, coord, color) in self.stones() {
             // get index in board
-            let index = coord.y * self.board_size + coord.x;
+            let index = usize::from(coord.y) * size + usize::from(coord.x);
             // pad to 2 digits and write with correct color
-            if curr_num % 2 == 0 {
-                // White to move
-                numbered_board[index as usize] = format!("{:0>2}", &curr_num.to_string().bold().black().on_bright_white());
+            numbered_board[index] = if !colored {
+                format!("{:0>2}", curr_num)
+            } else if color == Color::White {
+                format!("{:0>2}", &curr_num.to_string().bold().black().on_bright_white())
             } else {
-                // Black to move
-                numbered_board[index as usize] = format!("{:0>2}", &curr_num.to_string().bold().bright_white().on_black());
-            }
-            curr_num += 1;
+                format!("{:0>2}", &curr_num.to_string().bold().bright_white().on_black())
+            };
         }
         let mut output_string = String::new();
-        for y in 0..self.board_size {
-            for x in 0..self.board_size {
-                let index = y * self.board_size + x;
-                // push two dots so that you have enough room for 99 moves
-                output_string.push_str(&numbered_board[index as usize]);
+        for y in 0..size {
+            for x in 0..size {
+                output_string.push_str(&numbered_board[y * size + x]);
                 // add two spaces
                 output_string.push(' ');
                 output_string.push(' ');
@@ -74,34 +397,195 @@ impl fmt::Display for Game {
                 output_string.push(' ');
             }
         }
-        write!(f, "{}", output_string)
+        output_string
     }
-}
-
-impl Default for Game {
-    fn default() -> Game {
-        // 13 is default size
-        Game::new(13)
+    /// Draws the board with numbered moves like the `Display` implementation, but without any color,
+    /// so that it can be written to files and logs without ANSI escape codes. Odd-numbered moves are
+    /// Black's and even-numbered moves are White's, except that a swap makes Black's opening stone
+    /// White's.
+    pub fn to_plain_string(&self) -> String {
+        self.numbered_diagram(false)
     }
-}
-
-impl Game {
-    /// Returns a new Game of the given size.
-    pub fn new(size: u8) -> Game {
+    /// Returns the game as it would be with the board reflected across the long diagonal, which turns
+    /// each player's edges into the other's, so every piece also changes color: Black's wins become
+    /// White's and vice versa. This is useful for augmenting training data, or for looking at a game
+    /// from White's side of the board.
+    ///
+    /// Because Black always moves first, the reflection of Black's opening stone is played by
+    /// having White swap: the mirrored game starts with Black's original opening, a swap, and then
+    /// the rest of the moves reflected. If the original game started with a swap, the mirrored game
+    /// doesn't, and just starts with the original opening. Mirroring twice gives the original game.
+    pub fn mirrored(&self) -> Game {
+        let size = self.board_size;
+        let mut moves = vec![];
+        let mut rest = self.moves.iter();
+        if let Some(&opening) = rest.next() {
+            moves.push(opening);
+            if self.swapped() {
+                rest.next();
+            } else {
+                moves.push(Move::Swap);
+            }
+        }
+        moves.extend(rest.map(|&m| match m {
+            Move::Place(coord) => Move::Place(coord.reflect_long_diagonal(size)),
+            Move::Swap => Move::Swap,
+        }));
+        // adding or removing the swap changes the number of the move the game ended on
+        let num = ending_move_number(moves.len());
+        let ending = match self.ending {
+            GameEnding::NotApplicable => GameEnding::NotApplicable,
+            GameEnding::BlackResignation(_) => GameEnding::WhiteResignation(num),
+            GameEnding::BlackForfeit(_) => GameEnding::WhiteForfeit(num),
+            GameEnding::WhiteResignation(_) => GameEnding::BlackResignation(num),
+            GameEnding::WhiteForfeit(_) => GameEnding::BlackForfeit(num),
+        };
+        // the annotations move along with the swap being added or removed
+        let mut annotations = self.annotations.clone();
+        if annotations.len() > 1 && self.swapped() {
+            annotations.remove(1);
+        } else if annotations.len() > 1 {
+            annotations.insert(1, MoveAnnotation::default());
+        }
         Game {
             board_size: size,
-            board: Board::new(u16::from(size)),
-            moves: vec![]
+            moves,
+            // the board's reflection already changes the colors of the pieces
+            board: self.board.reflect_long_diagonal(),
+            ending,
+            annotations,
         }
     }
+    /// Writes the moves as a numbered list of Black's and White's moves, like a chess scoresheet:
+    /// for example, `"1. e5 swap  2. f6 d7  3. a1"`. Each pair of moves is numbered, and if Black
+    /// made the last move, the last pair only has Black's move in it. Swaps are written as `swap`.
+    pub fn move_notation(&self) -> String {
+        self.moves.chunks(2)
+            .zip(1..)
+            .map(|(pair, num)| {
+                let pair: Vec<String> = pair.iter().map(Move::to_string).collect();
+                format!("{}. {}", num, pair.join(" "))
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+    }
     /// Returns the current game's status. As this is updated on each move and stored, this function
-    /// incurs almost no runtime cost.
+    /// incurs almost no runtime cost. If a player resigned, their opponent has won.
     pub fn status(&self) -> GameStatus {
-        self.board.status()
+        match self.ending {
+            GameEnding::BlackResignation(_) | GameEnding::BlackForfeit(_) => GameStatus::WhiteWin,
+            GameEnding::WhiteResignation(_) | GameEnding::WhiteForfeit(_) => GameStatus::BlackWin,
+            GameEnding::NotApplicable => self.board.status(),
+        }
+    }
+    /// Returns how the game ended: the resignation and the move it happened on, if the next player
+    /// resigned with `resign`, and `GameEnding::NotApplicable` otherwise.
+    pub fn ending(&self) -> GameEnding {
+        self.ending
+    }
+    /// Returns the result of the game, combining the status, the ending, and the last move, or `None`
+    /// if the game is still ongoing.
+    pub fn outcome(&self) -> Option<Outcome> {
+        let (loser, by) = match self.ending {
+            GameEnding::BlackResignation(_) => (Color::Black, WinKind::Resignation),
+            GameEnding::BlackForfeit(_) => (Color::Black, WinKind::Forfeit),
+            GameEnding::WhiteResignation(_) => (Color::White, WinKind::Resignation),
+            GameEnding::WhiteForfeit(_) => (Color::White, WinKind::Forfeit),
+            GameEnding::NotApplicable => {
+                let winner = match self.board.status() {
+                    GameStatus::BlackWin => Color::Black,
+                    GameStatus::WhiteWin => Color::White,
+                    GameStatus::Ongoing => return None,
+                };
+                // only a placement can connect the edges
+                let deciding_move = match self.moves.last() {
+                    Some(&Move::Place(coord)) => Some(coord),
+                    _ => None,
+                };
+                return Some(Outcome { winner, deciding_move, by: WinKind::Connection });
+            }
+        };
+        Some(Outcome { winner: loser.opponent(), deciding_move: None, by })
+    }
+    /// Resigns the game for whichever player is next to move, so that their opponent wins. As
+    /// `GameEnding` expects, the resignation is numbered by the resigning player's move: Black
+    /// resigning instead of making their third move resigns on move 3. No more moves can be made
+    /// afterwards, and `undo_move` doesn't take the resignation back. If the game is already over,
+    /// returns an error and does nothing.
+    pub fn resign(&mut self) -> Result<(), MoveError> {
+        if self.status() != GameStatus::Ongoing {
+            return Err(MoveError::GameAlreadyOver(self.status()));
+        }
+        let move_number = ending_move_number(self.moves.len());
+        self.ending = match self.next_move_color() {
+            Color::Black => GameEnding::BlackResignation(move_number),
+            Color::White => GameEnding::WhiteResignation(move_number),
+        };
+        Ok(())
+    }
+    /// Returns the current position, for use with the analysis methods on `Board`. There's no
+    /// mutable version, because changing the board directly would make it disagree with the list of
+    /// moves: use `try_move` and `undo_move` instead.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    /// Returns an iterator over every position in the game, from the empty board through the board
+    /// after each move in turn, ending with the current position. The moves are replayed onto a
+    /// single board as the iterator advances, with each position yielded as a copy.
+    pub fn positions(&self) -> impl Iterator<Item = Board> + '_ {
+        let empty = Board::new(u16::from(self.board_size));
+        let replayed = self.moves.iter().enumerate().scan(empty.clone(), move |board, (i, m)| {
+            match *m {
+                Move::Place(coord) if i % 2 == 0 => {
+                    board.place_piece(coord, Color::Black);
+                }
+                Move::Place(coord) => {
+                    board.place_piece(coord, Color::White);
+                }
+                Move::Swap => {
+                    // the swap is always the second move, so the opening stone is the only one
+                    board.apply_swap();
+                }
+            }
+            Some(board.clone())
+        });
+        std::iter::once(empty).chain(replayed)
+    }
+    /// Returns the position after the first `move_index` moves, counting a swap, by replaying them:
+    /// the empty board for 0, or the current position for the number of moves or more.
+    pub fn position_at(&self, move_index: usize) -> Board {
+        self.positions().nth(move_index.min(self.moves.len())).unwrap()
+    }
+    /// Takes back every move after the first `move_index`, rebuilding the board, so that a different
+    /// variation can be played from that point. If any moves are taken back, so is a resignation or
+    /// forfeit. Does nothing if there are no more than `move_index` moves.
+    pub fn truncate(&mut self, move_index: usize) {
+        if move_index >= self.moves.len() {
+            return;
+        }
+        self.board = self.position_at(move_index);
+        self.moves.truncate(move_index);
+        self.annotations.truncate(move_index);
+        self.ending = GameEnding::NotApplicable;
+    }
+    /// Renders every position in the game as an SVG image with `Board::to_svg`, from the empty board
+    /// through the current position, for turning into an animation or a series of diagrams.
+    pub fn render_frames(&self, hex_size: f64) -> Vec<String> {
+        self.positions().map(|board| board.to_svg(hex_size)).collect()
+    }
+    /// Returns the number of the move that placed the stone at the given coordinate, counting from 1,
+    /// or `None` if the cell is empty. If White swapped, the opening stone is still move 1, even
+    /// though it has been reflected, and the swap itself is move 2.
+    pub fn move_number(&self, coord: Coord) -> Option<u16> {
+        self.stones().find(|&(_, stone, _)| stone == coord).map(|(num, _, _)| num)
+    }
+    /// Returns whether White swapped on their first move.
+    pub fn swapped(&self) -> bool {
+        self.moves.get(1) == Some(&Move::Swap)
     }
     /// Returns the color of the player next to move.
     pub fn next_move_color(&self) -> Color {
-        if self.moves.len() % 2 == 0 {
+        if self.moves.len().is_multiple_of(2) {
             // if even number of moves, last move was White, so next move is Black
             Color::Black
         } else {
@@ -109,25 +593,328 @@ impl Game {
             Color::White
         }
     }
-    /// Makes the next move of the game, using whichever color is next to play. If the given
-    /// coordinate is invalid (it already has a piece or is out of bounds), returns `false` and does
-    /// nothing. Otherwise, returns `true`.
+    /// Makes the given move, either placing a stone of whichever color is next to play or swapping.
+    /// If the move is invalid, returns an error describing why and does nothing.
+    pub fn play(&mut self, m: Move) -> Result<(), MoveError> {
+        match m {
+            Move::Place(coord) => self.try_move(coord),
+            Move::Swap => {
+                if self.status() != GameStatus::Ongoing {
+                    return Err(MoveError::GameAlreadyOver(self.status()));
+                }
+                if !matches!(self.moves[..], [Move::Place(_)]) {
+                    return Err(MoveError::InvalidSwap);
+                }
+                self.board.apply_swap();
+                self.moves.push(Move::Swap);
+                Ok(())
+            }
+        }
+    }
+    /// Makes the next move of the game, using whichever color is next to play. If the move is
+    /// invalid (the game is over, or the coordinate already has a piece or is out of bounds), returns
+    /// `false` and does nothing. Otherwise, returns `true`. Use `try_move` to find out why a move is
+    /// invalid.
     pub fn make_move(&mut self, coord: Coord) -> bool {
-        if self.board.place_piece(coord, self.next_move_color()) {
-            // move is valid, add to moves list and return true
-            self.moves.push(coord);
-            true
-        } else {
-            // move is invalid, do nothing and return false
-            false
+        self.try_move(coord).is_ok()
+    }
+    /// Makes the next move of the game, using whichever color is next to play. If the move is
+    /// invalid, returns an error describing why and does nothing.
+    pub fn try_move(&mut self, coord: Coord) -> Result<(), MoveError> {
+        if self.status() != GameStatus::Ongoing {
+            return Err(MoveError::GameAlreadyOver(self.status()));
+        }
+        if coord.x >= self.board_size || coord.y >= self.board_size {
+            return Err(MoveError::OutOfBounds);
+        }
+        match self.board.piece(coord) {
+            HexCell::Empty => {
+                // move is valid, add to moves list
+                self.board.place_piece(coord, self.next_move_color());
+                self.moves.push(Move::Place(coord));
+                Ok(())
+            }
+            cell => Err(MoveError::Occupied(cell)),
+        }
+    }
+    /// Plays a list of moves written as text, such as `"a1 c3, e5"`, as parsed by
+    /// `Coord::parse_list`. If any of the moves can't be parsed or played, returns an error and
+    /// leaves the game as it was before.
+    pub fn play_text(&mut self, s: &str) -> Result<(), MoveError> {
+        let coords = Coord::parse_list(s).map_err(|_| MoveError::InvalidNotation)?;
+        for (i, &coord) in coords.iter().enumerate() {
+            if let Err(e) = self.try_move(coord) {
+                // take back the moves that were played
+                for _ in 0..i {
+                    self.undo_move();
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+    /// Returns every cell the next player can move to, in row-major order: all of the empty cells if
+    /// the game is ongoing, and none once either player has won. Swapping isn't included even when it
+    /// is legal, because it isn't a move to a coordinate: see `swap`.
+    pub fn legal_moves(&self) -> Vec<Coord> {
+        if self.status() != GameStatus::Ongoing {
+            return vec![];
+        }
+        self.board.cells()
+            .filter(|&(_, cell)| cell == HexCell::Empty)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+    /// Returns the legal moves with only one of each pair of moves that are equivalent under the
+    /// 180-degree rotation, in row-major order. Moves are only equivalent when the position itself
+    /// looks the same rotated, as the empty board does: on size 3, this narrows the 9 opening moves
+    /// to 5. Otherwise, this is the same as `legal_moves`.
+    pub fn unique_first_moves(&self) -> Vec<Coord> {
+        let moves = self.legal_moves();
+        if self.board.rotate_180() != self.board {
+            return moves;
+        }
+        // of each pair, keep the one that comes first in row-major order
+        moves.into_iter()
+            .filter(|&coord| coord <= coord.rotate_180(self.board_size))
+            .collect()
+    }
+    /// Returns every cell where the next player would win immediately by playing, in row-major
+    /// order, or none if the game is already over.
+    pub fn winning_moves(&self) -> Vec<Coord> {
+        let mover = self.next_move_color();
+        self.legal_moves().into_iter()
+            .filter(|&coord| self.board.completes_connection(coord, mover))
+            .collect()
+    }
+    /// Returns every cell where the opponent of the next player would win immediately if it were
+    /// their turn, in row-major order, or none if the game is already over. If there are two or
+    /// more, the next player can't block them all and loses unless they win first.
+    pub fn must_block(&self) -> Vec<Coord> {
+        let opponent = self.next_move_color().opponent();
+        self.legal_moves().into_iter()
+            .filter(|&coord| self.board.completes_connection(coord, opponent))
+            .collect()
+    }
+    /// Returns the mustplay region: the cells the next player has to play in to stop the opponent's
+    /// threats, or none if the game is over. A threat is a cell where the opponent would either win
+    /// immediately or leave two or more cells where they'd win next, so that they can't all be
+    /// blocked. The region is the intersection, over every threat, of the threat's cells: the cell
+    /// itself and the winning cells it leaves. The next player's own winning moves are always
+    /// included. If the opponent has no threats, every legal move is in the region.
+    ///
+    /// This is the usual approximation, which only looks at the opponent's threats and only two of
+    /// their moves ahead. It can include losing moves, and it leaves out moves that make a threat
+    /// of the next player's own instead, which the opponent has to answer before playing theirs.
+    pub fn mustplay(&self) -> HashSet<Coord> {
+        let opponent = self.next_move_color().opponent();
+        let moves = self.legal_moves();
+        let mut region: HashSet<Coord> = moves.iter().cloned().collect();
+        for &threat in &moves {
+            let carrier: HashSet<Coord> = if self.board.completes_connection(threat, opponent) {
+                std::iter::once(threat).collect()
+            } else {
+                let mut board = self.board.clone();
+                board.place_piece(threat, opponent);
+                let wins: HashSet<Coord> = moves.iter().cloned()
+                    .filter(|&cell| board.completes_connection(cell, opponent))
+                    .collect();
+                if wins.len() < 2 {
+                    continue;
+                }
+                wins.into_iter().chain(std::iter::once(threat)).collect()
+            };
+            region.retain(|cell| carrier.contains(cell));
         }
-    }    
+        region.extend(self.winning_moves());
+        region
+    }
+    /// Counts the distinct sequences of `depth` moves that can be played from this position, like
+    /// perft in chess, which is useful for testing move generation. A sequence that ends the game
+    /// early counts once, without any moves after it. As in `legal_moves`, swaps aren't counted.
+    pub fn count_games(&self, depth: u8) -> u64 {
+        // play and take back moves on a single copy, rather than cloning for every move
+        fn count(game: &mut Game, depth: u8) -> u64 {
+            if depth == 0 || game.status() != GameStatus::Ongoing {
+                return 1;
+            }
+            let mut total = 0;
+            for coord in game.legal_moves() {
+                game.try_move(coord).expect("legal moves can be played");
+                total += count(game, depth - 1);
+                game.undo_move();
+            }
+            total
+        }
+        count(&mut self.clone(), depth)
+    }
+    /// Plays uniformly random moves on a copy of this game until someone wins, returning the final
+    /// status. This game is left unchanged. As Hex can't end in a draw, this always has a winner
+    /// unless the game is already over, in which case that status is returned.
+    #[cfg(feature = "rand")]
+    pub fn random_playout<R: Rng>(&self, rng: &mut R) -> GameStatus {
+        let mut game = self.clone();
+        // playing the empty cells in a random order is the same as picking each move at random
+        let mut moves = game.legal_moves();
+        moves.shuffle(rng);
+        for coord in moves {
+            if game.status() != GameStatus::Ongoing {
+                break;
+            }
+            game.make_move(coord);
+        }
+        game.status()
+    }
+    /// Returns the legal moves ordered from best to worst by a simple static evaluation. Moves that
+    /// win outright come first; the rest are scored by how much shorter the mover's cheapest
+    /// connection between their edges is than the opponent's after playing it, counting the number
+    /// of empty cells each still needs to fill. Ties are kept in row-major order.
+    fn ordered_moves(&self) -> Vec<Coord> {
+        let mover = self.next_move_color();
+        let opponent = mover.opponent();
+        let mut scored: Vec<(i32, Coord)> = self.legal_moves().into_iter().map(|coord| {
+            let mut board = self.board.clone();
+            board.place_piece(coord, mover);
+            if board.status() != GameStatus::Ongoing {
+                // the move wins outright
+                return (i32::MAX, coord);
+            }
+            // being completely cut off is worse than any distance
+            let distance = |color| i32::from(board.connection_distance(color).unwrap_or(u16::MAX));
+            (distance(opponent) - distance(mover), coord)
+        }).collect();
+        // the sort is stable, so ties stay in row-major order
+        scored.sort_by_key(|&(score, _)| Reverse(score));
+        scored.into_iter().map(|(_, coord)| coord).collect()
+    }
+    /// Suggests a move for the next player using a simple static evaluation, or returns `None` if the
+    /// game is already over. A move that wins outright is always suggested; otherwise, each legal
+    /// move is scored by how much shorter the mover's cheapest connection between their edges is
+    /// than the opponent's after playing it, counting the number of empty cells each still needs to
+    /// fill. Ties go to the earliest move in row-major order.
+    pub fn suggest_move(&self) -> Option<Coord> {
+        self.ordered_moves().first().cloned()
+    }
+    /// Solves the game by searching every line of play up to `max_depth` moves ahead, returning the
+    /// winner with perfect play and a best move for the next player: a winning move if they can
+    /// win, or otherwise the move that looks best by the same evaluation as `suggest_move`. If the
+    /// game is already over, returns its status with no move. Returns `None` if the winner can't be
+    /// determined within `max_depth` moves. Swaps aren't considered.
+    ///
+    /// This takes exponential time, so it's only practical for small boards (up to about 4x4) or
+    /// positions near the end of a game. The search is alpha-beta with moves ordered by
+    /// `suggest_move`'s evaluation: because the only possible results are wins, losses, and
+    /// positions that can't be solved within the depth, this stops at the first winning move found.
+    /// Solved positions are remembered by their Zobrist hashes, so positions reached by different
+    /// move orders are only solved once.
+    pub fn solve(&self, max_depth: u8) -> Option<(GameStatus, Option<Coord>)> {
+        /// The result of a search, from the perspective of the player to move.
+        #[derive(Copy, Clone, Eq, PartialEq)]
+        enum Outcome {
+            Win,
+            Loss,
+            Unknown,
+        }
+        // plays and takes back moves on a single copy of the game; only wins and losses, which
+        // don't depend on the depth, are remembered
+        fn search(game: &mut Game, depth: u8, solved: &mut HashMap<u64, Outcome>) -> (Outcome, Option<Coord>) {
+            if let Some(&outcome) = solved.get(&game.board.zobrist_hash()) {
+                return (outcome, None);
+            }
+            if depth == 0 {
+                return (Outcome::Unknown, None);
+            }
+            let moves = game.ordered_moves();
+            let mut outcome = Outcome::Loss;
+            for &coord in &moves {
+                game.try_move(coord).expect("legal moves can be played");
+                let reply = if game.status() != GameStatus::Ongoing {
+                    Outcome::Loss
+                } else {
+                    search(game, depth - 1, solved).0
+                };
+                game.undo_move();
+                match reply {
+                    Outcome::Loss => {
+                        solved.insert(game.board.zobrist_hash(), Outcome::Win);
+                        return (Outcome::Win, Some(coord));
+                    }
+                    Outcome::Unknown => outcome = Outcome::Unknown,
+                    Outcome::Win => {}
+                }
+            }
+            if outcome == Outcome::Loss {
+                solved.insert(game.board.zobrist_hash(), Outcome::Loss);
+            }
+            (outcome, moves.first().cloned())
+        }
+        if self.status() != GameStatus::Ongoing {
+            return Some((self.status(), None));
+        }
+        let mover = self.next_move_color();
+        let (outcome, best) = search(&mut self.clone(), max_depth, &mut HashMap::new());
+        let winner = match (outcome, mover) {
+            (Outcome::Unknown, _) => return None,
+            (Outcome::Win, Color::Black) | (Outcome::Loss, Color::White) => GameStatus::BlackWin,
+            (Outcome::Win, Color::White) | (Outcome::Loss, Color::Black) => GameStatus::WhiteWin,
+        };
+        Some((winner, best))
+    }
+    /// Takes back the last move of the game, removing it from the board and returning its
+    /// coordinate. If the last move was a swap, the swap is undone instead, returning Black's opening
+    /// stone to its original spot, and the coordinate of the removed White stone is returned. If no
+    /// moves have been made, returns `None` and does nothing.
+    pub fn undo_move(&mut self) -> Option<Coord> {
+        let m = self.moves.pop()?;
+        self.annotations.truncate(self.moves.len());
+        match m {
+            Move::Place(coord) => {
+                self.board.remove_piece(coord);
+                Some(coord)
+            }
+            Move::Swap => {
+                let opening = match self.moves[..] {
+                    [Move::Place(opening)] => opening,
+                    _ => unreachable!("a swap is always the second move"),
+                };
+                // swapping again reflects the stone back and makes it Black's
+                self.board.apply_swap();
+                Some(opening.reflect_long_diagonal(self.board_size))
+            }
+        }
+    }
+    /// Sets the annotation of the move at the given index in `moves`, counting from 0, replacing any
+    /// annotation it already had, and returns `true`. If there's no move at that index, returns
+    /// `false` and does nothing. Taking the move back also removes its annotation.
+    pub fn annotate(&mut self, move_index: usize, annotation: MoveAnnotation) -> bool {
+        if move_index >= self.moves.len() {
+            return false;
+        }
+        if self.annotations.len() <= move_index {
+            self.annotations.resize(move_index + 1, MoveAnnotation::default());
+        }
+        self.annotations[move_index] = annotation;
+        true
+    }
+    /// Returns the annotation of the move at the given index in `moves`, or `None` if there's no
+    /// move there or it hasn't been annotated.
+    pub fn annotation(&self, move_index: usize) -> Option<&MoveAnnotation> {
+        self.annotations.get(move_index).filter(|annotation| !annotation.is_empty())
+    }
+    /// Swaps colors as White's first move, according to the swap (pie) rule, and returns `true`.
+    /// Black's opening stone is reflected across the long diagonal into a White stone, and Black
+    /// moves next: this is equivalent to the players exchanging colors. If it isn't White's first
+    /// move, returns `false` and does nothing. This is the same as playing `Move::Swap`.
+    pub fn swap(&mut self) -> bool {
+        self.play(Move::Swap).is_ok()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use crate::testutil::Lcg;
 
     #[test]
     fn test_display() {
@@ -147,4 +934,666 @@ mod tests {
         println!();
         println!("{}", g);
     }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Game::try_new(0).unwrap_err(), BoardSizeError(0));
+        assert_eq!(Game::try_new(1).unwrap().board_size, 1);
+        assert_eq!(Game::try_new(26).unwrap().legal_moves().len(), 26 * 26);
+        assert_eq!(Game::try_new(27).unwrap_err(), BoardSizeError(27));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_large() {
+        Game::new(27);
+    }
+
+    #[test]
+    fn test_try_move() {
+        let mut g = Game::new(3);
+        assert_eq!(g.try_move(Coord{x: 3, y: 0}), Err(MoveError::OutOfBounds));
+        assert_eq!(g.try_move(Coord{x: 0, y: 1}), Ok(()));
+        assert_eq!(g.try_move(Coord{x: 0, y: 1}), Err(MoveError::Occupied(HexCell::Black)));
+        assert_eq!(g.try_move(Coord{x: 0, y: 0}), Ok(()));
+        assert_eq!(g.try_move(Coord{x: 0, y: 0}), Err(MoveError::Occupied(HexCell::White)));
+        assert!(!g.make_move(Coord{x: 0, y: 0}));
+        assert_eq!(g.moves.len(), 2);
+        g.make_move(Coord{x: 1, y: 1});
+        g.make_move(Coord{x: 1, y: 0});
+        g.make_move(Coord{x: 2, y: 1});
+        assert_eq!(g.status(), GameStatus::BlackWin);
+        assert_eq!(g.try_move(Coord{x: 2, y: 2}), Err(MoveError::GameAlreadyOver(GameStatus::BlackWin)));
+        assert_eq!(g.moves.len(), 5);
+    }
+
+    #[test]
+    fn test_from_moves() {
+        let moves = [Coord{x: 0, y: 1}, Coord{x: 0, y: 0}, Coord{x: 1, y: 1}, Coord{x: 1, y: 0}];
+        let g = Game::from_moves(3, &moves, false).unwrap();
+        assert_eq!(g.moves, moves.iter().map(|&c| Move::from(c)).collect::<Vec<_>>());
+        assert_eq!(g.board.piece(Coord{x: 1, y: 1}), HexCell::Black);
+
+        let swapped_moves = [Coord{x: 0, y: 1}, Coord{x: 0, y: 0}, Coord{x: 1, y: 1}, Coord{x: 2, y: 2}];
+        let g = Game::from_moves(3, &swapped_moves, true).unwrap();
+        assert!(g.swapped());
+        assert_eq!(g.moves.len(), 5);
+        assert_eq!(g.moves[1], Move::Swap);
+        assert_eq!(g.board.piece(Coord{x: 1, y: 0}), HexCell::White);
+        assert_eq!(g.board.piece(Coord{x: 0, y: 0}), HexCell::Black);
+        assert_eq!(g.board.piece(Coord{x: 1, y: 1}), HexCell::White);
+
+        let repeated = [Coord{x: 0, y: 1}, Coord{x: 0, y: 0}, Coord{x: 1, y: 1}, Coord{x: 0, y: 0}];
+        assert_eq!(Game::from_moves(3, &repeated, false).unwrap_err(),
+                   ReplayError::Move { index: 3, error: MoveError::Occupied(HexCell::White) });
+        assert_eq!(Game::from_moves(3, &[Coord{x: 5, y: 0}], false).unwrap_err(),
+                   ReplayError::Move { index: 0, error: MoveError::OutOfBounds });
+        assert_eq!(Game::from_moves(3, &[], true).unwrap_err(),
+                   ReplayError::Move { index: 0, error: MoveError::InvalidSwap });
+        // bad sizes are errors, not panics, with or without moves to check
+        assert_eq!(Game::from_moves(0, &[], false).unwrap_err(), ReplayError::BoardSize(BoardSizeError(0)));
+        assert_eq!(Game::from_moves(27, &moves, false).unwrap_err(), ReplayError::BoardSize(BoardSizeError(27)));
+    }
+
+    #[test]
+    fn test_from_moves_matches_replay() {
+        // random games of random lengths, some of which go on after a win or repeat a cell
+        let mut rng = Lcg::new(73);
+        for _ in 0..100 {
+            let mut moves = rng.shuffled_cells(5);
+            let length = rng.next() as usize % moves.len();
+            if rng.next() % 7 == 3 {
+                moves[length] = moves[0];
+            }
+            for &swapped in &[false, true] {
+                let moves = &moves[..=length];
+                match (Game::from_moves(5, moves, swapped), Game::replay(5, moves, swapped)) {
+                    (Ok(fast), Ok(slow)) => {
+                        assert_eq!(fast.moves, slow.moves);
+                        assert_eq!(fast.status(), slow.status());
+                        assert_eq!(fast.board, slow.board);
+                        // the same stones placed one at a time
+                        let mut board = Board::new(5);
+                        for (i, &coord) in moves.iter().enumerate() {
+                            // after a swap, Black is the one who plays second
+                            let color = if (i % 2 == 0) != (swapped && i > 0) { Color::Black } else { Color::White };
+                            board.place_piece(coord, color);
+                            if i == 0 && swapped {
+                                board.apply_swap();
+                            }
+                        }
+                        assert_eq!(fast.board, board);
+                        assert_eq!(fast.status(), board.status());
+                    }
+                    (fast, slow) => assert_eq!(fast.err(), slow.err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_text() {
+        let mut g = Game::new(5);
+        assert_eq!(g.play_text("A1, b2 c3"), Ok(()));
+        assert_eq!(g.moves, vec![Move::Place(Coord{x: 0, y: 0}), Move::Place(Coord{x: 1, y: 1}), Move::Place(Coord{x: 2, y: 2})]);
+        assert_eq!(g.board.piece(Coord{x: 1, y: 1}), HexCell::White);
+
+        assert_eq!(g.play_text("d4 e5 b2"), Err(MoveError::Occupied(HexCell::White)));
+        assert_eq!(g.play_text("d4 e9"), Err(MoveError::OutOfBounds));
+        assert_eq!(g.play_text("d4 5e"), Err(MoveError::InvalidNotation));
+        // failed lists leave the game untouched
+        assert_eq!(g.moves.len(), 3);
+        assert_eq!(g.board.piece(Coord{x: 3, y: 3}), HexCell::Empty);
+    }
+
+    #[test]
+    fn test_board() {
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 2, y: 2});
+        g.make_move(Coord{x: 1, y: 3});
+        assert_eq!(g.board().piece(Coord{x: 2, y: 2}), HexCell::Black);
+        assert_eq!(g.board().piece(Coord{x: 1, y: 3}), HexCell::White);
+        assert_eq!(g.board().piece(Coord{x: 0, y: 0}), HexCell::Empty);
+
+        // after a swap, the board holds White's reflected stone
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 1, y: 0});
+        g.swap();
+        assert_eq!(g.board().piece(Coord{x: 0, y: 1}), HexCell::White);
+        assert_eq!(g.board().piece(Coord{x: 1, y: 0}), HexCell::Empty);
+    }
+
+    #[test]
+    fn test_move_number() {
+        let mut g = Game::new(5);
+        g.play_text("c3 b4 d2").unwrap();
+        assert_eq!(g.move_number(Coord{x: 2, y: 2}), Some(1));
+        assert_eq!(g.move_number(Coord{x: 1, y: 3}), Some(2));
+        assert_eq!(g.move_number(Coord{x: 3, y: 1}), Some(3));
+        assert_eq!(g.move_number(Coord{x: 0, y: 0}), None);
+
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 1, y: 0});
+        g.swap();
+        g.make_move(Coord{x: 3, y: 3});
+        assert_eq!(g.move_number(Coord{x: 0, y: 1}), Some(1));
+        assert_eq!(g.move_number(Coord{x: 1, y: 0}), None);
+        // the swap is move 2
+        assert_eq!(g.move_number(Coord{x: 3, y: 3}), Some(3));
+    }
+
+    #[test]
+    fn test_render_frames() {
+        let mut g = Game::new(4);
+        g.make_move(Coord{x: 1, y: 2});
+        g.swap();
+        g.make_move(Coord{x: 3, y: 3});
+        let frames = g.render_frames(20.0);
+        assert_eq!(frames.len(), g.moves.len() + 1);
+        assert_eq!(frames[0], Board::new(4).to_svg(20.0));
+        assert_eq!(frames.last(), Some(&g.board().to_svg(20.0)));
+    }
+
+    #[test]
+    fn test_positions() {
+        let mut g = Game::new(5);
+        g.play_text("c3 b4 d2 a1").unwrap();
+        let positions: Vec<Board> = g.positions().collect();
+        assert_eq!(positions.len(), 5);
+        assert_eq!(positions[0], Board::new(5));
+        assert_eq!(positions[2].piece_count(Color::Black), 1);
+        assert_eq!(positions[2].piece(Coord{x: 1, y: 3}), HexCell::White);
+        assert_eq!(positions.last(), Some(g.board()));
+
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 1, y: 0});
+        g.swap();
+        g.make_move(Coord{x: 3, y: 3});
+        let positions: Vec<Board> = g.positions().collect();
+        assert_eq!(positions.len(), 4);
+        assert_eq!(positions[1].piece(Coord{x: 1, y: 0}), HexCell::Black);
+        assert_eq!(positions[2].piece(Coord{x: 1, y: 0}), HexCell::Empty);
+        assert_eq!(positions[2].piece(Coord{x: 0, y: 1}), HexCell::White);
+        assert_eq!(positions.last(), Some(g.board()));
+    }
+
+    #[test]
+    fn test_board_from_game() {
+        let mut g = Game::new(5);
+        g.play_text("c3 b4 d2").unwrap();
+        let board = Board::from(&g);
+        assert_eq!(board.piece_count(Color::Black), 2);
+        assert_eq!(board.piece_count(Color::White), 1);
+        assert_eq!(&board, g.board());
+        // the copy is independent of the game
+        g.make_move(Coord{x: 0, y: 0});
+        assert_eq!(board.piece_count(Color::White), 1);
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut g = Game::new(5);
+        g.play_text("c3 b4 d2 a1").unwrap();
+        assert_eq!(g.ending(), GameEnding::NotApplicable);
+        g.resign().unwrap();
+        assert_eq!(g.ending(), GameEnding::BlackResignation(3));
+        assert_eq!(g.status(), GameStatus::WhiteWin);
+        assert_eq!(g.moves.len(), 4);
+        assert_eq!(g.try_move(Coord{x: 4, y: 4}), Err(MoveError::GameAlreadyOver(GameStatus::WhiteWin)));
+        assert!(g.legal_moves().is_empty());
+        assert_eq!(g.resign(), Err(MoveError::GameAlreadyOver(GameStatus::WhiteWin)));
+
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 2, y: 2});
+        g.resign().unwrap();
+        assert_eq!(g.ending(), GameEnding::WhiteResignation(1));
+        assert_eq!(g.status(), GameStatus::BlackWin);
+
+        // move numbers past 255 stop there instead of wrapping
+        let mut g = Game::new(26);
+        g.moves = vec![Move::Swap; 600];
+        g.resign().unwrap();
+        assert_eq!(g.ending(), GameEnding::BlackResignation(255));
+    }
+
+    #[test]
+    fn test_outcome() {
+        let mut g = Game::new(3);
+        g.play_text("a2 b1 b2").unwrap();
+        assert_eq!(g.outcome(), None);
+        g.play_text("a3 c2").unwrap();
+        assert_eq!(g.outcome(), Some(Outcome {
+            winner: Color::Black,
+            deciding_move: Some(Coord{x: 2, y: 1}),
+            by: WinKind::Connection,
+        }));
+
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 2, y: 2});
+        g.resign().unwrap();
+        assert_eq!(g.outcome(), Some(Outcome {
+            winner: Color::Black,
+            deciding_move: None,
+            by: WinKind::Resignation,
+        }));
+    }
+
+    #[test]
+    fn test_move_notation() {
+        let mut g = Game::new(7);
+        assert_eq!(g.move_notation(), "");
+        g.make_move(Coord{x: 4, y: 4});
+        assert_eq!(g.move_notation(), "1. e5");
+        g.swap();
+        g.play_text("f6 d7").unwrap();
+        assert_eq!(g.move_notation(), "1. e5 swap  2. f6 d7");
+        g.play_text("a1").unwrap();
+        assert_eq!(g.move_notation(), "1. e5 swap  2. f6 d7  3. a1");
+    }
+
+    #[test]
+    fn test_mirrored() {
+        let mut g = Game::new(3);
+        g.play_text("a2 a1 b2 c1 c2").unwrap();
+        assert_eq!(g.status(), GameStatus::BlackWin);
+        let mirrored = g.mirrored();
+        assert_eq!(mirrored.status(), GameStatus::WhiteWin);
+        assert!(mirrored.swapped());
+        assert_eq!(mirrored.move_notation(), "1. a2 swap  2. a1 b2  3. a3 b3");
+        assert_eq!(mirrored.board(), &g.board().reflect_long_diagonal());
+        // the mirrored moves can be replayed to the same position
+        let mut replayed = Game::new(3);
+        for &m in &mirrored.moves {
+            replayed.play(m).unwrap();
+        }
+        assert_eq!(replayed.board(), mirrored.board());
+
+        // mirroring twice gets the original game back
+        let twice = mirrored.mirrored();
+        assert_eq!(twice.moves, g.moves);
+        assert_eq!(twice.board(), g.board());
+
+        let mut g = Game::new(3);
+        g.play_text("b2").unwrap();
+        g.resign().unwrap();
+        // Black resigns instead of moving after the swap
+        assert_eq!(g.mirrored().ending(), GameEnding::BlackResignation(2));
+        assert_eq!(g.mirrored().status(), GameStatus::WhiteWin);
+
+        // White resigning on move 2 becomes Black resigning on move 3 once the swap is added, as
+        // if the mirrored moves had been played and then resigned
+        let mut g = Game::new(3);
+        g.play_text("b2 a1 c3").unwrap();
+        g.resign().unwrap();
+        assert_eq!(g.ending(), GameEnding::WhiteResignation(2));
+        let mirrored = g.mirrored();
+        let mut replayed = Game::new(3);
+        for &m in &mirrored.moves {
+            replayed.play(m).unwrap();
+        }
+        replayed.resign().unwrap();
+        assert_eq!(mirrored.ending(), GameEnding::BlackResignation(3));
+        assert_eq!(replayed.ending(), mirrored.ending());
+        assert_eq!(mirrored.mirrored().ending(), g.ending());
+    }
+
+    #[test]
+    fn test_legal_moves() {
+        let mut g = Game::new(5);
+        assert_eq!(g.legal_moves().len(), 25);
+        g.make_move(Coord{x: 2, y: 2});
+        assert_eq!(g.legal_moves().len(), 24);
+        g.make_move(Coord{x: 3, y: 2});
+        assert!(!g.legal_moves().contains(&Coord{x: 2, y: 2}));
+        for x in 0..5 {
+            g.make_move(Coord{x, y: 0});
+            g.make_move(Coord{x, y: 4});
+        }
+        assert_eq!(g.status(), GameStatus::BlackWin);
+        assert!(g.legal_moves().is_empty());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_playout() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let g = Game::new(5);
+        let mut black_wins = 0;
+        let mut white_wins = 0;
+        for seed in 0..200 {
+            match g.random_playout(&mut StdRng::seed_from_u64(seed)) {
+                GameStatus::BlackWin => black_wins += 1,
+                GameStatus::WhiteWin => white_wins += 1,
+                GameStatus::Ongoing => panic!("playout ended without a winner"),
+            }
+        }
+        assert!(black_wins > 0);
+        assert!(white_wins > 0);
+        // the original game is untouched
+        assert!(g.moves.is_empty());
+    }
+
+    #[test]
+    fn test_count_games() {
+        let g = Game::new(3);
+        assert_eq!(g.count_games(0), 1);
+        assert_eq!(g.count_games(1), 9);
+        assert_eq!(g.count_games(2), 9 * 8);
+        assert_eq!(g.count_games(3), 9 * 8 * 7);
+
+        // every move Black can make here wins, so White can't reply
+        let mut g = Game::new(2);
+        g.play_text("a2 a1").unwrap();
+        assert_eq!(g.count_games(1), 2);
+        assert_eq!(g.count_games(2), 2);
+        // a finished game has nothing else to count
+        let mut g = Game::new(1);
+        g.make_move(Coord{x: 0, y: 0});
+        assert_eq!(g.count_games(5), 1);
+    }
+
+    #[test]
+    fn test_solve() {
+        // the first player always wins on an empty board
+        for size in 1..=3 {
+            let (winner, best) = Game::new(size).solve(size * size).unwrap();
+            assert_eq!(winner, GameStatus::BlackWin);
+            assert!(best.is_some());
+        }
+        // ... and Black wins 2x2 only by playing a2 or b1, the short diagonal
+        let (_, best) = Game::new(2).solve(4).unwrap();
+        assert!(best == Some(Coord{x: 0, y: 1}) || best == Some(Coord{x: 1, y: 0}));
+        let mut g = Game::new(2);
+        g.make_move(Coord{x: 0, y: 0});
+        assert_eq!(g.solve(3).unwrap().0, GameStatus::WhiteWin);
+
+        // too shallow to find the win
+        assert_eq!(Game::new(3).solve(2), None);
+
+        // a finished game just has its winner
+        let mut g = Game::new(1);
+        g.make_move(Coord{x: 0, y: 0});
+        assert_eq!(g.solve(0), Some((GameStatus::BlackWin, None)));
+    }
+
+    #[test]
+    fn test_suggest_move() {
+        let mut g = Game::new(5);
+        assert!(g.suggest_move().is_some());
+        for &(x, y) in &[(0, 2), (1, 1), (1, 2), (3, 3), (2, 2), (4, 4), (3, 2), (4, 1)] {
+            g.make_move(Coord{x, y});
+        }
+        // Black only needs (4, 2) to connect the left and right edges
+        assert_eq!(g.suggest_move(), Some(Coord{x: 4, y: 2}));
+        g.make_move(Coord{x: 4, y: 2});
+        assert_eq!(g.status(), GameStatus::BlackWin);
+        assert_eq!(g.suggest_move(), None);
+    }
+
+    #[test]
+    fn test_winning_moves() {
+        let mut g = Game::new(5);
+        assert!(g.winning_moves().is_empty());
+        for &(x, y) in &[(0, 2), (1, 1), (1, 2), (3, 3), (2, 2), (4, 4), (3, 2), (4, 1)] {
+            g.make_move(Coord{x, y});
+        }
+        assert_eq!(g.winning_moves(), vec![Coord{x: 4, y: 2}]);
+        // every winning move found this way really does win
+        for coord in g.legal_moves() {
+            let mut trial = g.clone();
+            trial.make_move(coord);
+            assert_eq!(trial.status() == GameStatus::BlackWin, coord == Coord{x: 4, y: 2});
+        }
+        g.make_move(Coord{x: 4, y: 2});
+        assert!(g.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn test_must_block() {
+        let mut g = Game::new(5);
+        for &(x, y) in &[(0, 2), (1, 1), (1, 2), (3, 3), (2, 2), (4, 1), (3, 2)] {
+            g.make_move(Coord{x, y});
+        }
+        // it's White's turn, and Black threatens to win at (4, 2)
+        assert_eq!(g.must_block(), vec![Coord{x: 4, y: 2}]);
+        g.make_move(Coord{x: 4, y: 2});
+        // White has no threats of their own, and Black's is blocked
+        assert!(g.must_block().is_empty());
+        assert!(g.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        let coords = [Coord{x: 2, y: 2}, Coord{x: 1, y: 3}, Coord{x: 3, y: 1}];
+        let a = Game::from_moves(5, &coords, false).unwrap();
+        let mut b = Game::new(5);
+        for &coord in &coords {
+            b.make_move(coord);
+        }
+        assert_eq!(a, b);
+        assert_ne!(a, Game::from_moves(5, &coords, true).unwrap());
+        assert_ne!(a, Game::from_moves(6, &coords, false).unwrap());
+        b.undo_move();
+        assert_ne!(a, b);
+
+        let mut cache = HashMap::new();
+        cache.insert(a.clone(), 1);
+        cache.insert(Game::from_moves(5, &coords, true).unwrap(), 2);
+        b.make_move(coords[2]);
+        assert_eq!(cache.get(&b), Some(&1));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_position_at() {
+        let mut g = Game::new(5);
+        for &(x, y) in &[(2, 2), (1, 3), (3, 1), (0, 4)] {
+            g.make_move(Coord{x, y});
+        }
+        assert_eq!(g.position_at(0), Board::new(5));
+        let mid = g.position_at(2);
+        assert_eq!(mid.piece(Coord{x: 2, y: 2}), HexCell::Black);
+        assert_eq!(mid.piece(Coord{x: 1, y: 3}), HexCell::White);
+        assert_eq!(mid.piece(Coord{x: 3, y: 1}), HexCell::Empty);
+        assert_eq!(&g.position_at(4), g.board());
+        assert_eq!(&g.position_at(100), g.board());
+
+        // truncating and playing a different variation
+        let original = g.clone();
+        g.truncate(100);
+        assert_eq!(g.moves, original.moves);
+        g.resign().unwrap();
+        g.truncate(2);
+        assert_eq!(g.moves.len(), 2);
+        assert_eq!(g.board(), &mid);
+        assert_eq!(g.status(), GameStatus::Ongoing);
+        assert!(g.make_move(Coord{x: 3, y: 1}));
+        assert_eq!(g.board(), &original.position_at(3));
+        g.truncate(0);
+        assert_eq!(g.board(), &Board::new(5));
+        assert!(g.moves.is_empty());
+    }
+
+    #[test]
+    fn test_unique_first_moves() {
+        let g = Game::new(3);
+        let moves = g.unique_first_moves();
+        assert_eq!(moves.len(), 5);
+        assert!(moves.contains(&Coord{x: 1, y: 1}));
+        assert!(!moves.contains(&Coord{x: 2, y: 2}));
+        assert_eq!(Game::new(4).unique_first_moves().len(), 8);
+        // once the position isn't symmetric, every move is different
+        let mut g = Game::new(3);
+        g.make_move(Coord{x: 0, y: 0});
+        assert_eq!(g.unique_first_moves(), g.legal_moves());
+    }
+
+    #[test]
+    fn test_mustplay() {
+        let mut g = Game::new(5);
+        assert_eq!(g.mustplay().len(), 25);
+        for &(x, y) in &[(0, 2), (1, 1), (1, 2), (3, 3), (2, 2), (4, 1), (3, 2)] {
+            g.make_move(Coord{x, y});
+        }
+        // White has to block Black's threat at (4, 2)
+        let expected: HashSet<Coord> = std::iter::once(Coord{x: 4, y: 2}).collect();
+        assert_eq!(g.mustplay(), expected);
+        g.make_move(Coord{x: 4, y: 2});
+        assert!(g.mustplay().len() > 1);
+
+        // on 2x2, Black's opening at the bottom left wins at either top cell, so White has lost
+        let mut g = Game::new(2);
+        g.make_move(Coord{x: 0, y: 1});
+        assert!(g.mustplay().is_empty());
+
+        // White's column on the right edge threatens to reach the bottom through d4, then c5 or d5,
+        // or through e4, then d5 or e5, so Black has to play d5 to stop both...
+        let mut g = Game::new(5);
+        for &(x, y) in &[(1, 4), (4, 0), (1, 3), (0, 3), (2, 3), (4, 2), (0, 4), (4, 1)] {
+            g.make_move(Coord{x, y});
+        }
+        let expected: HashSet<Coord> = std::iter::once(Coord{x: 3, y: 4}).collect();
+        assert_eq!(g.mustplay(), expected);
+        // ...but d4 isn't in the region, even though it threatens to win at e4 and so can't be
+        // ignored
+        g.make_move(Coord{x: 3, y: 3});
+        assert_eq!(g.must_block(), vec![Coord{x: 4, y: 3}]);
+    }
+
+    #[test]
+    fn test_to_plain_string() {
+        let mut g = Game::new(3);
+        g.make_move(Coord{x: 1, y: 1});
+        g.make_move(Coord{x: 2, y: 0});
+        let plain = g.to_plain_string();
+        assert!(!plain.contains('\x1b'));
+        assert_eq!(plain, "⋅⋅  ⋅⋅  02  \n\n  ⋅⋅  01  ⋅⋅  \n\n    ⋅⋅  ⋅⋅  ⋅⋅  \n\n      ");
+
+        // a large board doesn't overflow when indexing
+        let mut g = Game::new(26);
+        g.make_move(Coord{x: 25, y: 25});
+        assert!(g.to_plain_string().contains("01"));
+    }
+
+    #[test]
+    fn test_undo_move() {
+        let mut g = Game::new(5);
+        assert_eq!(g.undo_move(), None);
+        g.make_move(Coord{x: 0, y: 0});
+        g.make_move(Coord{x: 0, y: 2});
+        g.make_move(Coord{x: 0, y: 1});
+        g.make_move(Coord{x: 2, y: 4});
+        g.make_move(Coord{x: 1, y: 1});
+        g.make_move(Coord{x: 4, y: 1});
+        g.make_move(Coord{x: 1, y: 2});
+        g.make_move(Coord{x: 3, y: 0});
+        g.make_move(Coord{x: 2, y: 2});
+        g.make_move(Coord{x: 4, y: 3});
+        g.make_move(Coord{x: 3, y: 1});
+        g.make_move(Coord{x: 1, y: 4});
+        g.make_move(Coord{x: 4, y: 0});
+        assert_eq!(g.status(), GameStatus::BlackWin);
+        assert_eq!(g.next_move_color(), Color::White);
+
+        assert_eq!(g.undo_move(), Some(Coord{x: 4, y: 0}));
+        assert_eq!(g.status(), GameStatus::Ongoing);
+        assert_eq!(g.next_move_color(), Color::Black);
+        assert_eq!(g.moves.len(), 12);
+    }
+
+    #[test]
+    fn test_play() {
+        let mut g = Game::new(5);
+        assert_eq!(g.play(Move::Swap), Err(MoveError::InvalidSwap));
+        g.play(Move::Place(Coord{x: 1, y: 0})).unwrap();
+        g.play(Move::Swap).unwrap();
+        assert_eq!(g.moves, vec![Move::Place(Coord{x: 1, y: 0}), Move::Swap]);
+        assert!(g.swapped());
+        // the swap took White's turn, so Black moves next
+        assert_eq!(g.next_move_color(), Color::Black);
+        assert_eq!(g.play(Move::Swap), Err(MoveError::InvalidSwap));
+        g.play(Move::Place(Coord{x: 2, y: 2})).unwrap();
+        assert_eq!(g.board().piece(Coord{x: 2, y: 2}), HexCell::Black);
+        assert_eq!(g.next_move_color(), Color::White);
+        assert_eq!(g.play(Move::Place(Coord{x: 0, y: 1})), Err(MoveError::Occupied(HexCell::White)));
+
+        // undoing the swap puts the opening stone back
+        assert_eq!(g.undo_move(), Some(Coord{x: 2, y: 2}));
+        assert_eq!(g.undo_move(), Some(Coord{x: 0, y: 1}));
+        assert!(!g.swapped());
+        assert_eq!(g.board().piece(Coord{x: 1, y: 0}), HexCell::Black);
+        assert_eq!(g.next_move_color(), Color::White);
+        assert_eq!(Move::Swap.to_string(), "swap");
+        assert_eq!(Move::Place(Coord{x: 1, y: 0}).to_string(), "b1");
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut g = Game::new(13);
+        assert!(!g.swap());
+        g.make_move(Coord{x: 12, y: 0});
+        assert!(g.swap());
+        assert!(g.swapped());
+        assert!(!g.swap());
+
+        let mut black = 0;
+        let mut white = 0;
+        for x in 0..13 {
+            for y in 0..13 {
+                match g.board.piece(Coord{x, y}) {
+                    HexCell::Black => black += 1,
+                    HexCell::White => white += 1,
+                    HexCell::Empty => {}
+                }
+            }
+        }
+        assert_eq!((black, white), (0, 1));
+        assert_eq!(g.board.piece(Coord{x: 0, y: 12}), HexCell::White);
+
+        // Black moves after the swap, then White
+        assert_eq!(g.next_move_color(), Color::Black);
+        g.make_move(Coord{x: 6, y: 6});
+        assert_eq!(g.board.piece(Coord{x: 6, y: 6}), HexCell::Black);
+        assert_eq!(g.next_move_color(), Color::White);
+        assert!(!g.swap());
+
+        // undoing back past the swap restores the opening
+        assert_eq!(g.undo_move(), Some(Coord{x: 6, y: 6}));
+        assert_eq!(g.undo_move(), Some(Coord{x: 0, y: 12}));
+        assert!(!g.swapped());
+        assert_eq!(g.board.piece(Coord{x: 12, y: 0}), HexCell::Black);
+        assert_eq!(g.next_move_color(), Color::White);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 0, y: 4});
+        g.swap();
+        for coord in &[(0, 0), (0, 2), (0, 1), (2, 4), (1, 1), (4, 1), (1, 2), (3, 0), (2, 2)] {
+            g.make_move(Coord{x: coord.0, y: coord.1});
+        }
+        let json = serde_json::to_string(&g).unwrap();
+        let g2: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(g2.moves, g.moves);
+        assert!(g2.swapped());
+        assert_eq!(g2.status(), g.status());
+        assert_eq!(g2.next_move_color(), g.next_move_color());
+        for x in 0..5 {
+            for y in 0..5 {
+                assert_eq!(g2.board.piece(Coord{x, y}), g.board.piece(Coord{x, y}));
+            }
+        }
+
+        g.resign().unwrap();
+        let g2: Game = serde_json::from_str(&serde_json::to_string(&g).unwrap()).unwrap();
+        assert_eq!(g2.ending(), g.ending());
+        assert_eq!(g2.status(), GameStatus::BlackWin);
+
+        let bad = r#"{"board_size": 5, "moves": [{"x": 1, "y": 1}, {"x": 1, "y": 1}], "swapped": false}"#;
+        assert!(serde_json::from_str::<Game>(bad).is_err());
+    }
 }
@@ -1,13 +1,21 @@
 //! This file describes a struct to hold metadata about a game or set of games, loosely based on the
 //! Smart Game Format.
 
+use std::error;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Describes how and if a game ended: resignation, forfeit, or neither. Neither can mean either
 /// direct loss or that the given game is a partial game.  If a resignation or forfeit, includes the
 /// move on which the resignation or forfeit happened. This is numbered by move pair, not by
 /// move. Thus, the 3rd move by Black is really the 5th move of play.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GameEnding {
     /// The game did not finish or finished by direct loss.
+    #[default]
     NotApplicable,
     /// Black resigned on the given move.
     BlackResignation(u8),
@@ -19,9 +27,31 @@ pub enum GameEnding {
     WhiteForfeit(u8),
 }
 
+/// An error for constructing a date that doesn't exist on the calendar.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DateError {
+    /// The month is not between 1 and 12.
+    InvalidMonth(u8),
+    /// The day is not between 1 and the number of days in that month.
+    InvalidDay(u8),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DateError::InvalidMonth(month) => write!(f, "invalid month {}", month),
+            DateError::InvalidDay(day) => write!(f, "invalid day {}", day),
+        }
+    }
+}
+
+impl error::Error for DateError {}
+
 /// A set of properties and metadata relating to games of Hex, including resigns, forfeits, piece or
-/// color swaps, player names, and other notes.
-#[derive(Clone, Debug)]
+/// color swaps, player names, and other notes. By default, all of the strings are empty and the date
+/// is all zeros, meaning it is unknown.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameMetadata {
     /// Indicates whether White swapped colors on the second move. This crate does not handle the
     /// variant of Hex that has players swap pieces: it's equivalent to swapping colors and flipping
@@ -36,13 +66,148 @@ pub struct GameMetadata {
     /// Any comments on the game, as a string.
     pub comment: String,
     /// The year of the match, as an integer.
-    pub year: u8,
+    pub year: u16,
     /// The month of the match, as an integer 1-12.
     pub month: u8,
-    /// The day of the match, from 0 to 31.
+    /// The day of the match, from 1 to 31.
     pub day: u8,
     /// Indicates how the game ended and if either player resigned or forfeited. The game will still
     /// be read in even if the board has a win for either player or if either player resigned or
     /// forfeited, to indicate possible future variations.
     pub ending: GameEnding,
 }
+
+impl GameMetadata {
+    /// Returns metadata for a game played on the given date, with every other field left as the
+    /// default. Returns an error if the month is not between 1 and 12 or the day doesn't exist in
+    /// that month, accounting for leap years.
+    pub fn with_date(year: u16, month: u8, day: u8) -> Result<GameMetadata, DateError> {
+        GameMetadata::check_date(year, month, day)?;
+        Ok(GameMetadata {
+            year,
+            month,
+            day,
+            ..GameMetadata::default()
+        })
+    }
+    /// Returns a builder for metadata, starting from the defaults: empty names and comment, an
+    /// unknown date, no swap, and `GameEnding::NotApplicable`.
+    pub fn builder() -> GameMetadataBuilder {
+        GameMetadataBuilder { meta: GameMetadata::default() }
+    }
+    /// Returns an error if the month is not between 1 and 12 or the day doesn't exist in that month.
+    fn check_date(year: u16, month: u8, day: u8) -> Result<(), DateError> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if GameMetadata::is_leap_year(year) => 29,
+            2 => 28,
+            _ => return Err(DateError::InvalidMonth(month)),
+        };
+        if day == 0 || day > days_in_month {
+            Err(DateError::InvalidDay(day))
+        } else {
+            Ok(())
+        }
+    }
+    /// Returns whether the given year is a leap year in the Gregorian calendar.
+    fn is_leap_year(year: u16) -> bool {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    }
+}
+
+/// A builder for `GameMetadata`, created by `GameMetadata::builder`, for setting only the fields
+/// that are known.
+#[derive(Clone, Debug, Default)]
+pub struct GameMetadataBuilder {
+    meta: GameMetadata,
+}
+
+impl GameMetadataBuilder {
+    /// Sets Black's name.
+    pub fn black_name<S: Into<String>>(mut self, name: S) -> GameMetadataBuilder {
+        self.meta.black_name = name.into();
+        self
+    }
+    /// Sets White's name.
+    pub fn white_name<S: Into<String>>(mut self, name: S) -> GameMetadataBuilder {
+        self.meta.white_name = name.into();
+        self
+    }
+    /// Sets the comments on the game.
+    pub fn comment<S: Into<String>>(mut self, comment: S) -> GameMetadataBuilder {
+        self.meta.comment = comment.into();
+        self
+    }
+    /// Sets the date of the game, or returns an error if it doesn't exist, as in
+    /// `GameMetadata::with_date`.
+    pub fn date(mut self, year: u16, month: u8, day: u8) -> Result<GameMetadataBuilder, DateError> {
+        GameMetadata::check_date(year, month, day)?;
+        self.meta.year = year;
+        self.meta.month = month;
+        self.meta.day = day;
+        Ok(self)
+    }
+    /// Sets how the game ended.
+    pub fn ending(mut self, ending: GameEnding) -> GameMetadataBuilder {
+        self.meta.ending = ending;
+        self
+    }
+    /// Sets whether White swapped colors on the second move.
+    pub fn swapped(mut self, swapped: bool) -> GameMetadataBuilder {
+        self.meta.swapped = swapped;
+        self
+    }
+    /// Returns the metadata that has been built.
+    pub fn build(self) -> GameMetadata {
+        self.meta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_with_date() {
+        let meta = GameMetadata::with_date(2023, 7, 14).unwrap();
+        assert_eq!((meta.year, meta.month, meta.day), (2023, 7, 14));
+        assert_eq!(meta.ending, GameEnding::NotApplicable);
+        assert_eq!(GameMetadata::with_date(2023, 0, 14).unwrap_err(), DateError::InvalidMonth(0));
+        assert_eq!(GameMetadata::with_date(2023, 13, 14).unwrap_err(), DateError::InvalidMonth(13));
+        assert_eq!(GameMetadata::with_date(2023, 4, 0).unwrap_err(), DateError::InvalidDay(0));
+        assert_eq!(GameMetadata::with_date(2023, 4, 31).unwrap_err(), DateError::InvalidDay(31));
+    }
+
+    #[test]
+    fn test_builder() {
+        let meta = GameMetadata::builder().black_name("Alice").white_name(String::from("Bob")).build();
+        assert_eq!(meta.black_name, "Alice");
+        assert_eq!(meta.white_name, "Bob");
+        assert_eq!(meta.comment, "");
+        assert_eq!((meta.year, meta.month, meta.day), (0, 0, 0));
+        assert_eq!(meta.ending, GameEnding::NotApplicable);
+        assert!(!meta.swapped);
+
+        let meta = GameMetadata::builder()
+            .date(2024, 2, 29).unwrap()
+            .ending(GameEnding::WhiteResignation(12))
+            .swapped(true)
+            .comment("a long game")
+            .build();
+        assert_eq!((meta.year, meta.month, meta.day), (2024, 2, 29));
+        assert_eq!(meta.ending, GameEnding::WhiteResignation(12));
+        assert!(meta.swapped);
+        assert_eq!(meta.comment, "a long game");
+        assert_eq!(GameMetadata::builder().date(2023, 2, 29).unwrap_err(), DateError::InvalidDay(29));
+    }
+
+    #[test]
+    fn test_leap_day() {
+        assert!(GameMetadata::with_date(2024, 2, 29).is_ok());
+        assert!(GameMetadata::with_date(2000, 2, 29).is_ok());
+        assert_eq!(GameMetadata::with_date(2023, 2, 29).unwrap_err(), DateError::InvalidDay(29));
+        assert_eq!(GameMetadata::with_date(1900, 2, 29).unwrap_err(), DateError::InvalidDay(29));
+    }
+}
@@ -0,0 +1,141 @@
+//! A tree of variations branching off a game, for analysis, like the game trees in SGF. Each node
+//! after the root is a move, and its children are the different moves that were tried next. The
+//! first child of each node is the main line, and the others are variations of it. `Game` is still
+//! the type for playing a single line, and the tree hands out the line to any node as a `Game`.
+
+use crate::game::{Game, Move, MoveError};
+
+/// One position in the tree.
+#[derive(Clone, Debug)]
+struct Node {
+    /// The move that led here from the parent, or `None` for the root.
+    m: Option<Move>,
+    /// The index of the parent in the tree's list of nodes, or `None` for the root.
+    parent: Option<usize>,
+    /// The indices of the children, with the main line first.
+    children: Vec<usize>,
+}
+
+/// A tree of moves starting from the empty board, with a current node that can be moved around the
+/// tree. New variations are added at the current node.
+#[derive(Clone, Debug)]
+pub struct GameTree {
+    /// Every node in the tree, with the root first.
+    nodes: Vec<Node>,
+    /// The index of the current node.
+    current: usize,
+    /// The line of moves from the root to the current node.
+    game: Game,
+}
+
+impl GameTree {
+    /// Returns a tree for a game of the given size with only the root, which is the current node.
+    pub fn new(size: u8) -> GameTree {
+        GameTree {
+            nodes: vec![Node { m: None, parent: None, children: vec![] }],
+            current: 0,
+            game: Game::new(size),
+        }
+    }
+    /// Plays the move from the current node, adding it as the last of the current node's variations
+    /// if it isn't one already, and goes to the resulting node. If the move can't be made, returns
+    /// an error and does nothing.
+    pub fn add_variation(&mut self, m: Move) -> Result<(), MoveError> {
+        if self.forward(m) {
+            return Ok(());
+        }
+        self.game.play(m)?;
+        let index = self.nodes.len();
+        self.nodes.push(Node { m: Some(m), parent: Some(self.current), children: vec![] });
+        self.nodes[self.current].children.push(index);
+        self.current = index;
+        Ok(())
+    }
+    /// Returns the moves that have been tried from the current node, with the main line first.
+    pub fn variations(&self) -> Vec<Move> {
+        self.nodes[self.current].children.iter()
+            .map(|&child| self.nodes[child].m.unwrap())
+            .collect()
+    }
+    /// Goes to the child of the current node reached by the given move, returning whether it exists.
+    pub fn forward(&mut self, m: Move) -> bool {
+        let child = self.nodes[self.current].children.iter()
+            .cloned()
+            .find(|&child| self.nodes[child].m == Some(m));
+        match child {
+            Some(child) => {
+                self.game.play(m).expect("moves in the tree are legal");
+                self.current = child;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Goes to the parent of the current node, returning the move that was taken back, or `None` if
+    /// the current node is the root.
+    pub fn back(&mut self) -> Option<Move> {
+        let node = &self.nodes[self.current];
+        let parent = node.parent?;
+        let m = node.m;
+        self.game.undo_move();
+        self.current = parent;
+        m
+    }
+    /// Goes back to the root.
+    pub fn to_root(&mut self) {
+        while self.back().is_some() {}
+    }
+    /// Returns the line of moves from the root to the current node.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+    /// Returns the main line: the game given by following the first variation at every node,
+    /// starting from the root.
+    pub fn main_line(&self) -> Game {
+        let mut game = Game::new(self.game.board_size);
+        let mut node = &self.nodes[0];
+        while let Some(&child) = node.children.first() {
+            node = &self.nodes[child];
+            game.play(node.m.unwrap()).expect("moves in the tree are legal");
+        }
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::coord::Coord;
+
+    #[test]
+    fn test_two_branches() {
+        let place = |x, y| Move::Place(Coord{x, y});
+        let mut tree = GameTree::new(5);
+        tree.add_variation(place(2, 2)).unwrap();
+        tree.add_variation(place(1, 3)).unwrap();
+        tree.add_variation(place(3, 1)).unwrap();
+        // back to the first move, then try a swap instead
+        tree.back();
+        assert_eq!(tree.back(), Some(place(1, 3)));
+        tree.add_variation(Move::Swap).unwrap();
+        tree.add_variation(place(0, 0)).unwrap();
+        assert_eq!(tree.game().moves, vec![place(2, 2), Move::Swap, place(0, 0)]);
+        // an illegal move isn't added
+        assert_eq!(tree.add_variation(Move::Swap), Err(MoveError::InvalidSwap));
+
+        tree.to_root();
+        assert!(tree.game().moves.is_empty());
+        assert!(tree.back().is_none());
+        assert!(tree.forward(place(2, 2)));
+        assert_eq!(tree.variations(), vec![place(1, 3), Move::Swap]);
+        // adding an existing move goes to it instead of adding it again
+        tree.add_variation(Move::Swap).unwrap();
+        assert_eq!(tree.game().moves, vec![place(2, 2), Move::Swap]);
+        tree.back();
+        assert_eq!(tree.variations().len(), 2);
+        assert!(!tree.forward(place(4, 4)));
+
+        assert_eq!(tree.main_line().moves, vec![place(2, 2), place(1, 3), place(3, 1)]);
+    }
+}
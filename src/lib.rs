@@ -1,9 +1,49 @@
+//! An implementation of Hex. Coordinates, cells, and the fixed-size `ArrayBoard` and `BitBoard`
+//! only need `core` and `alloc`, so they're available without the default `std` feature, as on
+//! embedded devices. Everything else, including `Board` and `Game`, needs `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(feature = "std")]
 extern crate colored;
+#[cfg(feature = "std")]
 extern crate petgraph;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 pub mod coord;
+pub mod cell;
+pub mod arrayboard;
+pub mod bitboard;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
 pub mod board;
+#[cfg(feature = "std")]
 pub mod game;
+#[cfg(feature = "std")]
+pub mod gamemetadata;
+#[cfg(feature = "std")]
+pub mod gametree;
+#[cfg(feature = "std")]
+pub mod sgf;
+#[cfg(feature = "std")]
+pub mod trmph;
+#[cfg(feature = "std")]
+pub mod binary;
+#[cfg(feature = "std")]
+pub mod openingbook;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(all(test, feature = "std"))]
+mod testutil;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,183 @@
+//! A small book of recommended moves for known positions, for bots that want to play well-studied
+//! openings without searching them. Positions are looked up by the Zobrist hash of their canonical
+//! form along with the board size, so a position and its 180-degree rotation share an entry, while
+//! positions on boards of different sizes never do.
+//!
+//! A book can be loaded from text with one entry per line, written as the position in trmph format,
+//! then `=>`, then the recommended move:
+//!
+//! ```text
+//! #11, => f6
+//! #11,a1 => f6
+//! ```
+//!
+//! Blank lines are skipped.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::Board;
+use crate::coord::{Coord, ParseCoordError};
+use crate::game::Game;
+use crate::trmph::TrmphError;
+
+/// An error for loading an opening book from text. Each variant gives the line, counting from 1, on
+/// which the error occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpeningBookError {
+    /// The line isn't a position and a move separated by `=>`.
+    InvalidFormat(usize),
+    /// The position on the line isn't a valid trmph record.
+    Position(usize, TrmphError),
+    /// The move on the line isn't a valid coordinate.
+    Move(usize, ParseCoordError),
+    /// The move on the line isn't on the position's board.
+    OutOfBounds(usize),
+}
+
+impl fmt::Display for OpeningBookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OpeningBookError::InvalidFormat(line) => write!(f, "line {}: expected \"position => move\"", line),
+            OpeningBookError::Position(line, ref error) => write!(f, "line {}: {}", line, error),
+            OpeningBookError::Move(line, ref error) => write!(f, "line {}: {}", line, error),
+            OpeningBookError::OutOfBounds(line) => write!(f, "line {}: move is out of bounds", line),
+        }
+    }
+}
+
+impl error::Error for OpeningBookError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            OpeningBookError::InvalidFormat(_) | OpeningBookError::OutOfBounds(_) => None,
+            OpeningBookError::Position(_, ref error) => Some(error),
+            OpeningBookError::Move(_, ref error) => Some(error),
+        }
+    }
+}
+
+/// A map from positions to the move recommended in each.
+#[derive(Clone, Debug, Default)]
+pub struct OpeningBook {
+    /// The recommended moves, keyed by the board size and the Zobrist hash of the canonical
+    /// position, since the hash doesn't include the size. Each move is stored as it would be played
+    /// on the canonical position.
+    moves: HashMap<(u16, u64), Coord>,
+}
+
+/// Returns the canonical form of the board, and whether it's the 180-degree rotation rather than the
+/// board itself.
+fn canonical(board: &Board) -> (Board, bool) {
+    let canonical = board.canonical();
+    let rotated = canonical != *board;
+    (canonical, rotated)
+}
+
+impl OpeningBook {
+    /// Returns an empty book.
+    pub fn new() -> OpeningBook {
+        OpeningBook::default()
+    }
+    /// Returns the number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+    /// Returns whether the book has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+    /// Records the move to play in the given position, replacing any move already recorded for it or
+    /// its 180-degree rotation, and returns true. If the move isn't on the board, returns false and
+    /// does nothing.
+    pub fn insert(&mut self, board: &Board, coord: Coord) -> bool {
+        if u16::from(coord.x) >= board.size || u16::from(coord.y) >= board.size {
+            return false;
+        }
+        let (canonical, rotated) = canonical(board);
+        let coord = if rotated { coord.rotate_180(board.size as u8) } else { coord };
+        self.moves.insert((board.size, canonical.zobrist_hash()), coord);
+        true
+    }
+    /// Returns the move recorded for the given position, or `None` if it isn't in the book.
+    pub fn lookup(&self, board: &Board) -> Option<Coord> {
+        let (canonical, rotated) = canonical(board);
+        self.moves.get(&(board.size, canonical.zobrist_hash()))
+            .map(|&coord| if rotated { coord.rotate_180(board.size as u8) } else { coord })
+    }
+}
+
+impl FromStr for OpeningBook {
+    type Err = OpeningBookError;
+
+    /// Loads a book from text with a `position => move` entry on each line.
+    fn from_str(s: &str) -> Result<OpeningBook, OpeningBookError> {
+        let mut book = OpeningBook::new();
+        for (i, line) in s.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.splitn(2, "=>");
+            let (position, coord) = match (parts.next(), parts.next()) {
+                (Some(position), Some(coord)) => (position.trim(), coord.trim()),
+                _ => return Err(OpeningBookError::InvalidFormat(i)),
+            };
+            let game = Game::from_trmph(position).map_err(|error| OpeningBookError::Position(i, error))?;
+            let coord = Coord::from_str(coord).map_err(|error| OpeningBookError::Move(i, error))?;
+            if !book.insert(game.board(), coord) {
+                return Err(OpeningBookError::OutOfBounds(i));
+            }
+        }
+        Ok(book)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let mut book = OpeningBook::new();
+        let mut board = Board::new(5);
+        board.place_piece(Coord{x: 0, y: 0}, crate::board::Color::Black);
+        assert!(book.insert(&board, Coord{x: 2, y: 2}));
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.lookup(&board), Some(Coord{x: 2, y: 2}));
+        assert_eq!(book.lookup(&Board::new(5)), None);
+        // the rotated position gets the rotated move
+        assert_eq!(book.lookup(&board.rotate_180()), Some(Coord{x: 2, y: 2}));
+        book.insert(&board, Coord{x: 1, y: 3});
+        assert_eq!(book.lookup(&board.rotate_180()), Some(Coord{x: 3, y: 1}));
+        assert!(!book.insert(&board, Coord{x: 5, y: 0}));
+        assert_eq!(book.len(), 1);
+    }
+
+    #[test]
+    fn test_sizes() {
+        // the empty boards have the same hash, but are different positions
+        let book: OpeningBook = "#11, => f6\n#5, => c3".parse().unwrap();
+        assert_eq!(book.len(), 2);
+        assert_eq!(book.lookup(&Board::new(11)), Some(Coord{x: 5, y: 5}));
+        assert_eq!(book.lookup(&Board::new(5)), Some(Coord{x: 2, y: 2}));
+        assert_eq!(book.lookup(&Board::new(7)), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let book: OpeningBook = "#5, => c3\n\n#5,a1 => b4\n".parse().unwrap();
+        assert_eq!(book.len(), 2);
+        assert_eq!(book.lookup(&Board::new(5)), Some(Coord{x: 2, y: 2}));
+        assert_eq!(book.lookup(Game::from_trmph("#5,e5").unwrap().board()), Some(Coord{x: 3, y: 1}));
+        assert_eq!("#5,a1".parse::<OpeningBook>().unwrap_err(), OpeningBookError::InvalidFormat(1));
+        assert_eq!("\n#5,a1 => a0".parse::<OpeningBook>().unwrap_err(),
+                   OpeningBookError::Move(2, ParseCoordError::InvalidFormat));
+        assert_eq!("#5,zz => a1".parse::<OpeningBook>().unwrap_err(),
+                   OpeningBookError::Position(1, TrmphError::InvalidFormat));
+        // a move off the board, including on a position that's stored rotated
+        assert_eq!("#5, => f1".parse::<OpeningBook>().unwrap_err(), OpeningBookError::OutOfBounds(1));
+        assert_eq!("#5,e5 => z9".parse::<OpeningBook>().unwrap_err(), OpeningBookError::OutOfBounds(1));
+    }
+}
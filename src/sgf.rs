@@ -0,0 +1,658 @@
+//! Reading and writing Hex games in the [Smart Game
+//! Format](https://www.red-bean.com/sgf/) (SGF). Hex is game number 11 in SGF. Moves use SGF's
+//! two-letter coordinates, column then row, so that `Coord{x: 2, y: 0}` is written `ca`, rather
+//! than the `c1` notation used by `Coord`'s `Display`.
+//!
+//! A swap is written as its own node after Black's opening move, removing the opening stone with
+//! `AE` and adding the reflected White stone with `AW`, with a `C[swap]` comment so that it reads
+//! sensibly in other SGF viewers. When reading, HexGui's `W[swap-pieces]` is also accepted.
+//!
+//! Move annotations are written on each move's node, with the comment as `C`, a good move as
+//! `TE[1]`, and a bad move as `BM[1]`.
+//!
+//! Only the main line of an SGF record is read: other variations are skipped.
+
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::board::{Color, GameStatus};
+use crate::coord::Coord;
+use crate::game::{ending_move_number, Game, Move, MoveAnnotation};
+use crate::gamemetadata::{GameEnding, GameMetadata};
+
+/// An error for reading a game from SGF.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SgfError {
+    /// The record isn't syntactically valid SGF.
+    InvalidFormat,
+    /// The record's `GM` property has the given value instead of 11, so it isn't a game of Hex.
+    NotHex(String),
+    /// The record doesn't give the board size with `SZ`.
+    MissingSize,
+    /// The property with the given identifier has the given value, which can't be understood.
+    InvalidProperty(String, String),
+    /// The move at the given index in the move list was played to an occupied or out-of-bounds cell.
+    IllegalMove(usize, Coord),
+    /// Reading the input failed with an I/O error of the given kind.
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for SgfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SgfError::InvalidFormat => write!(f, "invalid SGF syntax"),
+            SgfError::NotHex(ref gm) => write!(f, "SGF game type {} is not Hex", gm),
+            SgfError::MissingSize => write!(f, "SGF record has no board size"),
+            SgfError::InvalidProperty(ref id, ref value) => {
+                write!(f, "invalid value {} for SGF property {}", value, id)
+            }
+            SgfError::IllegalMove(index, coord) => write!(f, "illegal move {} at index {}", coord, index),
+            SgfError::Io(kind) => write!(f, "error reading SGF: {:?}", kind),
+        }
+    }
+}
+
+impl error::Error for SgfError {}
+
+/// A single SGF property: an identifier with one or more values.
+type Property = (String, Vec<String>);
+
+/// A simple parser over the characters of an SGF record, keeping only the nodes of the main line.
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Parser<'a> {
+        Parser { chars: s.chars().peekable() }
+    }
+    /// Skips any whitespace, returning the next character without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+        self.chars.peek().cloned()
+    }
+    /// Consumes the next non-whitespace character, failing if it isn't the one given.
+    fn expect(&mut self, c: char) -> Result<(), SgfError> {
+        if self.peek() == Some(c) {
+            self.chars.next();
+            Ok(())
+        } else {
+            Err(SgfError::InvalidFormat)
+        }
+    }
+    /// Parses a game tree, returning the nodes along its main line: the first variation is followed
+    /// whenever there's a choice.
+    fn game_tree(&mut self) -> Result<Vec<Vec<Property>>, SgfError> {
+        self.expect('(')?;
+        let mut nodes = vec![];
+        while self.peek() == Some(';') {
+            self.chars.next();
+            nodes.push(self.node()?);
+        }
+        if self.peek() == Some('(') {
+            nodes.extend(self.game_tree()?);
+            // skip any other variations
+            while self.peek() == Some('(') {
+                self.game_tree()?;
+            }
+        }
+        self.expect(')')?;
+        Ok(nodes)
+    }
+    /// Parses the properties of a node, after its semicolon.
+    fn node(&mut self) -> Result<Vec<Property>, SgfError> {
+        let mut properties = vec![];
+        while self.peek().is_some_and(|c| c.is_ascii_uppercase()) {
+            let mut ident = String::new();
+            while let Some(c) = self.chars.peek().cloned().filter(|c| c.is_ascii_uppercase()) {
+                ident.push(c);
+                self.chars.next();
+            }
+            let mut values = vec![];
+            while self.peek() == Some('[') {
+                self.chars.next();
+                values.push(self.value()?);
+            }
+            if values.is_empty() {
+                return Err(SgfError::InvalidFormat);
+            }
+            properties.push((ident, values));
+        }
+        Ok(properties)
+    }
+    /// Parses a property value after its opening bracket, through its closing bracket.
+    fn value(&mut self) -> Result<String, SgfError> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some(']') => return Ok(value),
+                Some('\\') => match self.chars.next() {
+                    // an escaped newline is a soft line break, and is removed
+                    Some('\n') => {}
+                    Some(c) => value.push(c),
+                    None => return Err(SgfError::InvalidFormat),
+                },
+                Some(c) => value.push(c),
+                None => return Err(SgfError::InvalidFormat),
+            }
+        }
+    }
+}
+
+/// Decodes a coordinate in SGF's two-letter format, without checking it against the board size.
+fn parse_sgf_coord(value: &str) -> Option<Coord> {
+    let bytes = value.as_bytes();
+    if bytes.len() == 2 && bytes.iter().all(u8::is_ascii_lowercase) {
+        Some(Coord{x: bytes[0] - b'a', y: bytes[1] - b'a'})
+    } else {
+        None
+    }
+}
+
+/// Reads the date from an SGF `DT` value of the form `YYYY`, `YYYY-MM`, or `YYYY-MM-DD` into the
+/// metadata, returning `None` if it isn't a valid date.
+fn parse_date(value: &str, meta: &mut GameMetadata) -> Option<()> {
+    let parts: Vec<&str> = value.split('-').collect();
+    let year = parts.first()?.parse().ok()?;
+    let month = parts.get(1).map_or(Some(1), |m| m.parse().ok())?;
+    let day = parts.get(2).map_or(Some(1), |d| d.parse().ok())?;
+    if parts.len() > 3 {
+        return None;
+    }
+    let dated = GameMetadata::with_date(year, month, day).ok()?;
+    meta.year = dated.year;
+    meta.month = if parts.len() > 1 { dated.month } else { 0 };
+    meta.day = if parts.len() > 2 { dated.day } else { 0 };
+    Some(())
+}
+
+/// Encodes a coordinate in SGF's two-letter format: the column, then the row, each from `a`.
+fn sgf_coord(coord: Coord) -> String {
+    let mut s = String::new();
+    s.push((b'a' + coord.x) as char);
+    s.push((b'a' + coord.y) as char);
+    s
+}
+
+/// Escapes an SGF property value, which can't contain unescaped `]` or `\`.
+fn escape(value: &str) -> String {
+    let mut s = String::new();
+    for c in value.chars() {
+        if c == ']' || c == '\\' {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s
+}
+
+/// Returns the SGF properties for the player names and date, leaving out any that are unknown.
+fn player_and_date_properties(meta: &GameMetadata) -> String {
+    let mut properties = String::new();
+    if !meta.black_name.is_empty() {
+        properties.push_str(&format!("PB[{}]", escape(&meta.black_name)));
+    }
+    if !meta.white_name.is_empty() {
+        properties.push_str(&format!("PW[{}]", escape(&meta.white_name)));
+    }
+    if meta.year != 0 {
+        properties.push_str(&format!("DT[{:04}-{:02}-{:02}]", meta.year, meta.month, meta.day));
+    }
+    properties
+}
+
+/// Returns the SGF result for a resignation or forfeit, or `None` if the game didn't end that way.
+fn ending_result(ending: GameEnding) -> Option<&'static str> {
+    match ending {
+        GameEnding::BlackResignation(_) => Some("W+R"),
+        GameEnding::BlackForfeit(_) => Some("W+F"),
+        GameEnding::WhiteResignation(_) => Some("B+R"),
+        GameEnding::WhiteForfeit(_) => Some("B+F"),
+        GameEnding::NotApplicable => None,
+    }
+}
+
+/// Writes the metadata as the properties of an SGF root node, without the `;` that starts the node,
+/// so that it can be put in front of a list of moves: the player names, date, and result of a
+/// resignation or forfeit as `PB`, `PW`, `DT`, and `RE`, and the comment as `C`. The swap, which
+/// SGF records as a move, is noted at the end of the comment instead. Unknown properties are left
+/// out.
+impl fmt::Display for GameMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", player_and_date_properties(self))?;
+        if let Some(result) = ending_result(self.ending) {
+            write!(f, "RE[{}]", result)?;
+        }
+        let mut comment = self.comment.clone();
+        if self.swapped {
+            if !comment.is_empty() {
+                comment.push('\n');
+            }
+            comment.push_str("White swapped.");
+        }
+        if !comment.is_empty() {
+            write!(f, "C[{}]", escape(&comment))?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Returns this game as an SGF record, using the given metadata for the player names, date,
+    /// comments, and result. The result is only written if the game ended by resignation or
+    /// forfeit, or if one of the players has won on the board.
+    pub fn to_sgf(&self, meta: &GameMetadata) -> String {
+        let mut sgf = format!("(;FF[4]GM[11]SZ[{}]", self.board_size);
+        sgf.push_str(&player_and_date_properties(meta));
+        // a resignation in the game itself counts if the metadata doesn't say otherwise
+        let ending = match meta.ending {
+            GameEnding::NotApplicable => self.ending(),
+            ending => ending,
+        };
+        let result = ending_result(ending).or(match self.status() {
+            GameStatus::BlackWin => Some("B+"),
+            GameStatus::WhiteWin => Some("W+"),
+            GameStatus::Ongoing => None,
+        });
+        if let Some(result) = result {
+            sgf.push_str(&format!("RE[{}]", result));
+        }
+        if !meta.comment.is_empty() {
+            sgf.push_str(&format!("C[{}]", escape(&meta.comment)));
+        }
+        for (i, m) in self.moves.iter().enumerate() {
+            match *m {
+                Move::Place(coord) => {
+                    let color = if i % 2 == 0 { "B" } else { "W" };
+                    sgf.push_str(&format!(";{}[{}]", color, sgf_coord(coord)));
+                }
+                Move::Swap => {
+                    if let Some(&Move::Place(opening)) = self.moves.first() {
+                        let reflected = opening.reflect_long_diagonal(self.board_size);
+                        sgf.push_str(&format!(";AE[{}]AW[{}]", sgf_coord(opening), sgf_coord(reflected)));
+                    }
+                }
+            }
+            let annotation = self.annotation(i);
+            let comment = annotation.map_or("", |a| a.comment.as_str());
+            if *m == Move::Swap {
+                // the swap's comment comes first on its own line
+                let separator = if comment.is_empty() { "" } else { "\n" };
+                sgf.push_str(&format!("C[swap{}{}]", separator, escape(comment)));
+            } else if !comment.is_empty() {
+                sgf.push_str(&format!("C[{}]", escape(comment)));
+            }
+            if annotation.is_some_and(|a| a.good) {
+                sgf.push_str("TE[1]");
+            }
+            if annotation.is_some_and(|a| a.bad) {
+                sgf.push_str("BM[1]");
+            }
+        }
+        sgf.push(')');
+        sgf
+    }
+    /// Reads a game and its metadata from an SGF record, replaying each move of the main line. The
+    /// player names, date, root comment, and resignations or forfeits are read into the metadata.
+    /// Fails if the record isn't a game of Hex, is malformed, or has an illegal move.
+    pub fn from_sgf(s: &str) -> Result<(Game, GameMetadata), SgfError> {
+        let mut parser = Parser::new(s);
+        let nodes = parser.game_tree()?;
+        if parser.peek().is_some() {
+            return Err(SgfError::InvalidFormat);
+        }
+        let root = nodes.first().ok_or(SgfError::InvalidFormat)?;
+        let root_value = |ident: &str| {
+            root.iter().find(|(id, _)| id == ident).map(|(_, values)| values[0].clone())
+        };
+        if let Some(gm) = root_value("GM") {
+            if gm.trim() != "11" {
+                return Err(SgfError::NotHex(gm));
+            }
+        }
+        let size_value = root_value("SZ").ok_or(SgfError::MissingSize)?;
+        let invalid_size = || SgfError::InvalidProperty("SZ".to_string(), size_value.clone());
+        let size = size_value.trim().parse().map_err(|_| invalid_size())?;
+        let mut game = Game::try_new(size).map_err(|_| invalid_size())?;
+
+        let mut meta = GameMetadata {
+            black_name: root_value("PB").unwrap_or_default(),
+            white_name: root_value("PW").unwrap_or_default(),
+            comment: root_value("C").unwrap_or_default(),
+            ..GameMetadata::default()
+        };
+        if let Some(date) = root_value("DT") {
+            if parse_date(&date, &mut meta).is_none() {
+                return Err(SgfError::InvalidProperty("DT".to_string(), date));
+            }
+        }
+
+        for node in &nodes {
+            let moves_before = game.moves.len();
+            for (ident, values) in node {
+                let invalid = || SgfError::InvalidProperty(ident.clone(), values[0].clone());
+                let color = match ident.as_str() {
+                    "B" => Some(Color::Black),
+                    "W" => Some(Color::White),
+                    _ => None,
+                };
+                // a move by the player who isn't next would leave the wrong color on the board
+                if color.is_some_and(|color| color != game.next_move_color()) {
+                    return Err(invalid());
+                }
+                match ident.as_str() {
+                    "B" | "W" if values[0] == "swap-pieces" => {
+                        let swapped = game.swap();
+                        if !swapped {
+                            return Err(invalid());
+                        }
+                    }
+                    "B" | "W" => {
+                        let coord = parse_sgf_coord(&values[0]).ok_or_else(invalid)?;
+                        if !game.make_move(coord) {
+                            return Err(SgfError::IllegalMove(game.moves.len(), coord));
+                        }
+                    }
+                    // a swap removes Black's opening stone, which is the only one on the board
+                    "AE" if game.moves.len() == 1 && !game.swapped() => {
+                        game.swap();
+                    }
+                    _ => {}
+                }
+            }
+            // the annotation belongs to the move made in this node, if any
+            if game.moves.len() > moves_before {
+                let value = |ident: &str| {
+                    node.iter().find(|(id, _)| id == ident).map(|(_, values)| values[0].as_str())
+                };
+                let mut comment = value("C").unwrap_or("");
+                if game.moves.last() == Some(&Move::Swap) {
+                    comment = comment.strip_prefix("swap").unwrap_or(comment);
+                    comment = comment.strip_prefix('\n').unwrap_or(comment);
+                }
+                let annotation = MoveAnnotation {
+                    comment: comment.to_string(),
+                    good: value("TE").is_some(),
+                    bad: value("BM").is_some(),
+                };
+                game.annotate(game.moves.len() - 1, annotation);
+            }
+        }
+        meta.swapped = game.swapped();
+
+        // resignations and forfeits are numbered by the loser's move
+        let loser_move = ending_move_number(game.moves.len());
+        meta.ending = match root_value("RE").as_deref() {
+            Some("W+R") | Some("W+Resign") => GameEnding::BlackResignation(loser_move),
+            Some("W+F") | Some("W+T") | Some("W+Time") | Some("W+Forfeit")
+                => GameEnding::BlackForfeit(loser_move),
+            Some("B+R") | Some("B+Resign") => GameEnding::WhiteResignation(loser_move),
+            Some("B+F") | Some("B+T") | Some("B+Time") | Some("B+Forfeit")
+                => GameEnding::WhiteForfeit(loser_move),
+            _ => GameEnding::NotApplicable,
+        };
+        Ok((game, meta))
+    }
+    /// Reads a collection of SGF records, such as a file of many games, parsing and yielding each
+    /// game as soon as its record is complete rather than loading the whole input first. Records
+    /// can be separated by any whitespace, including blank lines. After an I/O error or a record
+    /// cut off by the end of the input, the iterator yields that error and then stops.
+    pub fn read_collection<R: BufRead>(r: R) -> impl Iterator<Item = Result<(Game, GameMetadata), SgfError>> {
+        Records { reader: r, line: String::new(), pos: 0, done: false }
+    }
+}
+
+/// An iterator over the SGF records in a reader, which splits the input into records by tracking
+/// the nesting of parentheses outside property values.
+struct Records<R> {
+    reader: R,
+    /// The line currently being read.
+    line: String,
+    /// The byte offset in `line` of the first character not yet read.
+    pos: usize,
+    /// Whether the input has ended or failed.
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<(Game, GameMetadata), SgfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = String::new();
+        let mut depth = 0;
+        let mut in_value = false;
+        let mut escaped = false;
+        while !self.done {
+            if self.pos == self.line.len() {
+                self.line.clear();
+                self.pos = 0;
+                match self.reader.read_line(&mut self.line) {
+                    Ok(0) => {
+                        self.done = true;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(SgfError::Io(e.kind())));
+                    }
+                }
+            }
+            for c in self.line[self.pos..].chars() {
+                self.pos += c.len_utf8();
+                if depth == 0 {
+                    if c.is_whitespace() {
+                        continue;
+                    } else if c != '(' {
+                        // stray text between records: skip the rest of the line
+                        self.pos = self.line.len();
+                        return Some(Err(SgfError::InvalidFormat));
+                    }
+                }
+                record.push(c);
+                if escaped {
+                    escaped = false;
+                } else if in_value {
+                    match c {
+                        '\\' => escaped = true,
+                        ']' => in_value = false,
+                        _ => {}
+                    }
+                } else {
+                    match c {
+                        '[' => in_value = true,
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Some(Game::from_sgf(&record));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if record.is_empty() {
+            None
+        } else {
+            Some(Err(SgfError::InvalidFormat))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_to_sgf() {
+        let mut g = Game::new(5);
+        g.make_move(Coord{x: 0, y: 0});
+        g.make_move(Coord{x: 2, y: 1});
+        g.make_move(Coord{x: 4, y: 3});
+        let mut meta = GameMetadata::with_date(2023, 7, 4).unwrap();
+        meta.black_name = "Alice".to_string();
+        meta.white_name = "Bob [guest]".to_string();
+        assert_eq!(g.to_sgf(&meta),
+                   "(;FF[4]GM[11]SZ[5]PB[Alice]PW[Bob [guest\\]]DT[2023-07-04];B[aa];W[cb];B[ed])");
+
+        // a resignation in the game is used if the metadata doesn't have one
+        g.resign().unwrap();
+        assert!(g.to_sgf(&GameMetadata::default()).contains("RE[B+R]"));
+    }
+
+    #[test]
+    fn test_to_sgf_swap() {
+        let mut g = Game::new(7);
+        g.make_move(Coord{x: 1, y: 5});
+        g.swap();
+        g.make_move(Coord{x: 3, y: 3});
+        let meta = GameMetadata {
+            ending: GameEnding::WhiteResignation(2),
+            ..GameMetadata::default()
+        };
+        assert_eq!(g.to_sgf(&meta),
+                   "(;FF[4]GM[11]SZ[7]RE[B+R];B[bf];AE[bf]AW[fb]C[swap];B[dd])");
+    }
+
+    #[test]
+    fn test_sgf_round_trip() {
+        let mut g = Game::new(7);
+        g.make_move(Coord{x: 1, y: 5});
+        g.swap();
+        for coord in &[(3, 3), (2, 4), (6, 0), (0, 6)] {
+            g.make_move(Coord{x: coord.0, y: coord.1});
+        }
+        let mut meta = GameMetadata::with_date(2019, 2, 28).unwrap();
+        meta.black_name = "Alice".to_string();
+        meta.white_name = "Bob".to_string();
+        meta.comment = "a [bracketed] \\ comment".to_string();
+        meta.ending = GameEnding::BlackResignation(4);
+
+        let (g2, meta2) = Game::from_sgf(&g.to_sgf(&meta)).unwrap();
+        assert_eq!(g2.board_size, 7);
+        assert_eq!(g2.moves, g.moves);
+        assert!(g2.swapped());
+        assert_eq!(g2.status(), g.status());
+        assert_eq!(g2.next_move_color(), g.next_move_color());
+        assert_eq!(meta2.black_name, meta.black_name);
+        assert_eq!(meta2.white_name, meta.white_name);
+        assert_eq!(meta2.comment, meta.comment);
+        assert_eq!((meta2.year, meta2.month, meta2.day), (2019, 2, 28));
+        assert_eq!(meta2.ending, GameEnding::BlackResignation(4));
+        assert!(meta2.swapped);
+    }
+
+    #[test]
+    fn test_from_sgf_variations() {
+        let sgf = "(;FF[4]GM[11]SZ[5]\n  ;B[cc];W[bd](;B[ab];W[ba])(;B[ee]))";
+        let (g, _) = Game::from_sgf(sgf).unwrap();
+        let moves = [Coord{x: 2, y: 2}, Coord{x: 1, y: 3}, Coord{x: 0, y: 1}, Coord{x: 1, y: 0}];
+        assert_eq!(g.moves, moves.iter().map(|&c| Move::from(c)).collect::<Vec<_>>());
+
+        let (g, _) = Game::from_sgf("(;GM[11]SZ[5];B[cc];W[swap-pieces])").unwrap();
+        assert!(g.swapped());
+    }
+
+    #[test]
+    fn test_annotations() {
+        let mut game = Game::new(5);
+        game.make_move(Coord{x: 2, y: 2});
+        game.swap();
+        game.make_move(Coord{x: 0, y: 4});
+        game.make_move(Coord{x: 1, y: 1});
+        let annotation = MoveAnnotation { comment: "too far from the center".to_string(), good: false, bad: true };
+        assert!(game.annotate(2, annotation.clone()));
+        assert!(game.annotate(1, MoveAnnotation { comment: "of course".to_string(), good: true, bad: false }));
+        assert!(!game.annotate(4, annotation.clone()));
+        assert_eq!(game.annotation(2), Some(&annotation));
+        assert_eq!(game.annotation(3), None);
+
+        let sgf = game.to_sgf(&GameMetadata::default());
+        assert!(sgf.contains(";B[ae]C[too far from the center]BM[1];"));
+        assert!(sgf.contains("C[swap\nof course]TE[1]"));
+        let (read, _) = Game::from_sgf(&sgf).unwrap();
+        assert_eq!(read.annotation(0), None);
+        assert_eq!(read.annotation(1).unwrap().comment, "of course");
+        assert!(read.annotation(1).unwrap().good);
+        assert_eq!(read.annotation(2), Some(&annotation));
+        assert_eq!(read.annotation(3), None);
+
+        // taking the move back removes its annotation
+        game.undo_move();
+        game.undo_move();
+        game.make_move(Coord{x: 0, y: 4});
+        assert_eq!(game.annotation(2), None);
+    }
+
+    #[test]
+    fn test_metadata_display() {
+        let meta = GameMetadata::builder()
+            .black_name("Alice")
+            .white_name("B[o]b")
+            .date(2023, 7, 4).unwrap()
+            .ending(GameEnding::BlackResignation(20))
+            .build();
+        let header = meta.to_string();
+        assert!(header.contains("PB[Alice]"));
+        assert!(header.contains("PW[B[o\\]b]"));
+        assert_eq!(header, "PB[Alice]PW[B[o\\]b]DT[2023-07-04]RE[W+R]");
+        assert_eq!(GameMetadata::default().to_string(), "");
+        let swapped = GameMetadata::builder().comment("fun").swapped(true).build();
+        assert_eq!(swapped.to_string(), "C[fun\nWhite swapped.]");
+
+        // the header can be put in front of a list of moves
+        let (game, read) = Game::from_sgf(&format!("(;GM[11]SZ[5]{};B[cc])", header)).unwrap();
+        assert_eq!(game.moves.len(), 1);
+        assert_eq!(read.white_name, "B[o]b");
+        assert_eq!(read.ending, GameEnding::BlackResignation(1));
+    }
+
+    #[test]
+    fn test_read_collection() {
+        let input = "(;GM[11]SZ[5];B[cc];W[ba])\n\n\n(;GM[11]SZ[4]C[a (tricky\\] comment];B[aa]\n;W[swap-pieces])\n\n";
+        let games: Vec<_> = Game::read_collection(input.as_bytes()).collect();
+        assert_eq!(games.len(), 2);
+        let (first, _) = games[0].clone().unwrap();
+        assert_eq!(first.board_size, 5);
+        assert_eq!(first.moves, vec![Move::Place(Coord{x: 2, y: 2}), Move::Place(Coord{x: 1, y: 0})]);
+        let (second, meta) = games[1].clone().unwrap();
+        assert_eq!(second.board_size, 4);
+        assert!(second.swapped());
+        assert!(meta.swapped);
+
+        let truncated: Vec<_> = Game::read_collection("(;GM[11]SZ[5];B[cc])\n(;GM[11]SZ[5]".as_bytes()).collect();
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated[0].is_ok());
+        assert_eq!(truncated[1].as_ref().err(), Some(&SgfError::InvalidFormat));
+    }
+
+    #[test]
+    fn test_from_sgf_errors() {
+        assert_eq!(Game::from_sgf("(;GM[1]SZ[19];B[cc])").unwrap_err(), SgfError::NotHex("1".to_string()));
+        assert_eq!(Game::from_sgf("(;GM[11];B[cc])").unwrap_err(), SgfError::MissingSize);
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[27];B[cc])").unwrap_err(),
+                   SgfError::InvalidProperty("SZ".to_string(), "27".to_string()));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[cc]").unwrap_err(), SgfError::InvalidFormat);
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[c3])").unwrap_err(),
+                   SgfError::InvalidProperty("B".to_string(), "c3".to_string()));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[cc];W[cc])").unwrap_err(),
+                   SgfError::IllegalMove(1, Coord{x: 2, y: 2}));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[cc];W[fa])").unwrap_err(),
+                   SgfError::IllegalMove(1, Coord{x: 5, y: 0}));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5]DT[2023-02-30])").unwrap_err(),
+                   SgfError::InvalidProperty("DT".to_string(), "2023-02-30".to_string()));
+        // moves by the player who isn't next
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];W[cc])").unwrap_err(),
+                   SgfError::InvalidProperty("W".to_string(), "cc".to_string()));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[cc];B[aa])").unwrap_err(),
+                   SgfError::InvalidProperty("B".to_string(), "aa".to_string()));
+        assert_eq!(Game::from_sgf("(;GM[11]SZ[5];B[cc];B[swap-pieces])").unwrap_err(),
+                   SgfError::InvalidProperty("B".to_string(), "swap-pieces".to_string()));
+    }
+}
@@ -0,0 +1,30 @@
+//! Helpers shared by the tests of several modules.
+
+use alloc::vec::Vec;
+
+use crate::coord::Coord;
+
+/// A linear congruential generator, so that tests can play pseudorandom games that are the same on
+/// every run, without needing the `rand` feature.
+pub(crate) struct Lcg(u32);
+
+impl Lcg {
+    /// Returns a generator starting from the given seed.
+    pub(crate) fn new(seed: u32) -> Lcg {
+        Lcg(seed)
+    }
+    /// Returns the next number in the sequence, which has 16 bits.
+    pub(crate) fn next(&mut self) -> u32 {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        self.0 >> 16
+    }
+    /// Returns every cell of a board of the given size, in a pseudorandom order.
+    pub(crate) fn shuffled_cells(&mut self, size: u8) -> Vec<Coord> {
+        let mut cells: Vec<Coord> = (0..size).flat_map(|y| (0..size).map(move |x| Coord{x, y})).collect();
+        for i in (1..cells.len()).rev() {
+            let j = self.next() as usize % (i + 1);
+            cells.swap(i, j);
+        }
+        cells
+    }
+}
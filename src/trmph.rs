@@ -0,0 +1,112 @@
+//! Reading and writing Hex games in the trmph format used by [trmph.com](https://trmph.com) and
+//! shared widely on Little Golem and HexWiki. A record is the board size followed by the moves run
+//! together in `Coord`'s `Display` notation, such as `#13,g7f8`. A swap is written as `swap` in
+//! place of White's first move. Records are usually shared as the fragment of a trmph URL, such as
+//! `https://trmph.com/hex/board#13,g7f8`, and anything before the `#` is ignored when reading.
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::coord::Coord;
+use crate::game::{Game, Move, MoveError};
+
+/// An error for reading a game in trmph format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrmphError {
+    /// The record isn't a size and a list of moves separated by a comma, or one of the moves isn't
+    /// a valid coordinate.
+    InvalidFormat,
+    /// The record's board size is the given text, which isn't a valid size.
+    InvalidSize(String),
+    /// The move at the given index in the move list, counting a swap, couldn't be made.
+    IllegalMove(usize, MoveError),
+}
+
+impl fmt::Display for TrmphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TrmphError::InvalidFormat => write!(f, "invalid trmph record"),
+            TrmphError::InvalidSize(ref size) => write!(f, "invalid trmph board size {}", size),
+            TrmphError::IllegalMove(index, error) => write!(f, "illegal move at index {}: {}", index, error),
+        }
+    }
+}
+
+impl error::Error for TrmphError {}
+
+impl Game {
+    /// Returns this game in trmph format, starting with `#` and the board size.
+    pub fn to_trmph(&self) -> String {
+        let moves: String = self.moves.iter().map(|m| m.to_string()).collect();
+        format!("#{},{}", self.board_size, moves)
+    }
+    /// Reads a game in trmph format, either on its own or as part of a trmph URL.
+    pub fn from_trmph(s: &str) -> Result<Game, TrmphError> {
+        let record = s.trim().rsplit('#').next().unwrap_or("");
+        let mut parts = record.splitn(2, ',');
+        let size = parts.next().unwrap_or("");
+        let moves = parts.next().ok_or(TrmphError::InvalidFormat)?;
+        let mut game = size.parse().ok()
+            .and_then(|size| Game::try_new(size).ok())
+            .ok_or_else(|| TrmphError::InvalidSize(size.to_string()))?;
+        let mut rest = moves;
+        let mut index = 0;
+        while !rest.is_empty() {
+            let m = if rest.starts_with("swap") {
+                rest = &rest[4..];
+                Move::Swap
+            } else {
+                // a column letter followed by the row number, which ends at the next letter
+                let letters = rest.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+                let end = letters + rest[letters..].chars().take_while(|c| c.is_ascii_digit()).count();
+                if letters != 1 || end == letters {
+                    return Err(TrmphError::InvalidFormat);
+                }
+                let coord = Coord::from_str(&rest[..end]).map_err(|_| TrmphError::InvalidFormat)?;
+                rest = &rest[end..];
+                Move::Place(coord)
+            };
+            game.play(m).map_err(|error| TrmphError::IllegalMove(index, error))?;
+            index += 1;
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut g = Game::new(13);
+        for &(x, y) in &[(6, 6), (5, 7), (10, 2), (3, 11)] {
+            g.make_move(Coord{x, y});
+        }
+        assert_eq!(g.to_trmph(), "#13,g7f8k3d12");
+        let read = Game::from_trmph(&format!("https://trmph.com/hex/board{}", g.to_trmph())).unwrap();
+        assert_eq!(read.board_size, 13);
+        assert_eq!(read.moves, g.moves);
+
+        let mut swapped = Game::new(13);
+        swapped.make_move(Coord{x: 0, y: 12});
+        swapped.swap();
+        swapped.make_move(Coord{x: 4, y: 4});
+        assert_eq!(swapped.to_trmph(), "#13,a13swape5");
+        assert_eq!(Game::from_trmph(&swapped.to_trmph()).unwrap().moves, swapped.moves);
+        assert_eq!(Game::from_trmph("#5,").unwrap().moves, vec![]);
+    }
+
+    #[test]
+    fn test_from_trmph_errors() {
+        assert_eq!(Game::from_trmph("#13").unwrap_err(), TrmphError::InvalidFormat);
+        assert_eq!(Game::from_trmph("#30,a1").unwrap_err(), TrmphError::InvalidSize("30".to_string()));
+        assert_eq!(Game::from_trmph("#5,a1bb").unwrap_err(), TrmphError::InvalidFormat);
+        assert_eq!(Game::from_trmph("#5,a1a1").unwrap_err(),
+                   TrmphError::IllegalMove(1, MoveError::Occupied(crate::board::HexCell::Black)));
+        assert_eq!(Game::from_trmph("#5,a1b2swap").unwrap_err(), TrmphError::IllegalMove(2, MoveError::InvalidSwap));
+        assert_eq!(Game::from_trmph("#5,f1").unwrap_err(), TrmphError::IllegalMove(0, MoveError::OutOfBounds));
+    }
+}
@@ -0,0 +1,67 @@
+//! JavaScript bindings for `Game` through `wasm-bindgen`, for playing in a browser. Enabled by the
+//! `wasm` feature.
+
+use wasm_bindgen::prelude::*;
+
+use crate::board::GameStatus;
+use crate::coord::Coord;
+use crate::game::Game;
+
+/// A game that can be created and played from JavaScript.
+#[wasm_bindgen]
+pub struct WasmGame {
+    game: Game,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// Creates a new game of the given size, which must be between 1 and 26.
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: u8) -> Result<WasmGame, JsValue> {
+        Game::try_new(size)
+            .map(|game| WasmGame { game })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+    /// Plays the next player's piece at the given column and row, both counting from 0, returning
+    /// whether the move was made: it isn't if the cell is out of bounds or occupied, or the game is
+    /// over.
+    pub fn make_move(&mut self, x: u8, y: u8) -> bool {
+        self.game.try_move(Coord{x, y}).is_ok()
+    }
+    /// Returns the status of the game: 0 if it's ongoing, 1 if Black has won, or 2 if White has
+    /// won.
+    pub fn status(&self) -> u8 {
+        match self.game.status() {
+            GameStatus::Ongoing => 0,
+            GameStatus::BlackWin => 1,
+            GameStatus::WhiteWin => 2,
+        }
+    }
+    /// Renders the current position as an SVG image with the given hex width, as in
+    /// `Board::to_svg`.
+    pub fn to_svg(&self, size: f64) -> String {
+        self.game.board().to_svg(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    // runs under wasm-bindgen-test on wasm32, and as a normal test everywhere else
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn test_play() {
+        let mut game = WasmGame { game: Game::new(2) };
+        assert_eq!(game.status(), 0);
+        assert!(game.make_move(0, 1));
+        assert!(!game.make_move(0, 1));
+        assert!(game.make_move(0, 0));
+        assert!(game.make_move(1, 0));
+        assert_eq!(game.status(), 1);
+        assert!(game.to_svg(20.0).starts_with("<svg"));
+    }
+}